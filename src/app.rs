@@ -1,12 +1,26 @@
-use std::time::Duration;
+use std::{path::Path, time::Duration};
+
+use crossterm::style::Stylize;
 
 use crate::{
     error::Result,
     event::Controls,
-    game::Game,
+    game::{
+        bot::Bot,
+        ghost::Ghost,
+        input::parse_script,
+        lan::{LanVersus, DEFAULT_PORT},
+        replay::Replay,
+        versus::Versus,
+        Difficulty, Game, GameMode, Modifier, Modifiers, DEFAULT_DURATION, SPRINT_LENGTHS,
+    },
+    rules::Rules,
     screenln,
-    storage::{Leaderboard, PlayerData, Storage, Upgrades},
-    utility::{GameTimer, InputFreeze},
+    storage::{Loadouts, PlayerData, RecallResults, Records, Settings, Storage, Upgrades},
+    strategem::{Strategem, StrategemClass, ALL_STRATEGEMS},
+    training::{practice::Practice, quiz::Quiz, recall::Recall},
+    tui::menu::Selection,
+    utility::{format_day, format_duration, GameTimer},
 };
 
 pub const LOGO: &str = r#"     _             _                                  _                    
@@ -18,13 +32,29 @@ pub const LOGO: &str = r#"     _             _
                             __/ |                                          
                            |___/ "#;
 
+/// How long the main menu waits for a key before the attract mode demo starts
+const ATTRACT_DELAY: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Default)]
 pub enum Screen {
     #[default]
     Main,
-    Game,
+    Demo,
+    Modes,
+    Game(GameMode),
+    HotSeat,
+    Versus,
+    Lan,
+    GhostRace,
+    Training,
+    Practice,
+    Quiz,
+    Recall,
+    Loadouts,
     Leaderboard,
+    Replays,
     Upgrades,
+    Settings,
     DeleteData,
 }
 
@@ -33,18 +63,70 @@ impl Screen {
         *self = Self::Main;
     }
 
-    pub fn set_game(&mut self) {
-        *self = Self::Game;
+    pub fn set_demo(&mut self) {
+        *self = Self::Demo;
+    }
+
+    pub fn set_modes(&mut self) {
+        *self = Self::Modes;
+    }
+
+    pub fn set_game(&mut self, mode: GameMode) {
+        *self = Self::Game(mode);
+    }
+
+    pub fn set_hot_seat(&mut self) {
+        *self = Self::HotSeat;
+    }
+
+    pub fn set_versus(&mut self) {
+        *self = Self::Versus;
+    }
+
+    pub fn set_lan(&mut self) {
+        *self = Self::Lan;
+    }
+
+    pub fn set_ghost_race(&mut self) {
+        *self = Self::GhostRace;
+    }
+
+    pub fn set_training(&mut self) {
+        *self = Self::Training;
+    }
+
+    pub fn set_practice(&mut self) {
+        *self = Self::Practice;
+    }
+
+    pub fn set_quiz(&mut self) {
+        *self = Self::Quiz;
+    }
+
+    pub fn set_recall(&mut self) {
+        *self = Self::Recall;
+    }
+
+    pub fn set_loadouts(&mut self) {
+        *self = Self::Loadouts;
     }
 
     pub fn set_leaderboard(&mut self) {
         *self = Self::Leaderboard;
     }
 
+    pub fn set_replays(&mut self) {
+        *self = Self::Replays;
+    }
+
     pub fn set_upgrades(&mut self) {
         *self = Self::Upgrades;
     }
 
+    pub fn set_settings(&mut self) {
+        *self = Self::Settings;
+    }
+
     pub fn set_delete_data(&mut self) {
         *self = Self::DeleteData;
     }
@@ -53,9 +135,19 @@ impl Screen {
 pub struct App {
     screen: Screen,
     player: PlayerData,
-    leaderboard: Leaderboard,
+    records: Records,
+    seed: Option<u64>,
     is_running: bool,
     upgrades: Upgrades,
+    loadouts: Loadouts,
+    loadout: Vec<Strategem>,
+    recall: RecallResults,
+    settings: Settings,
+    rules: Rules,
+    /// Run length given with `--duration`, used over the saved setting
+    duration: Option<Duration>,
+    /// Why the rules file could not be read, shown on the main menu
+    rules_error: Option<String>,
 }
 
 impl App {
@@ -63,22 +155,54 @@ impl App {
         crate::utility::setup_data_dir()?;
 
         let player = PlayerData::open()?;
-        let leaderboard = Leaderboard::open()?;
+        let records = Records::open()?;
         let upgrades = Upgrades::open()?;
+        let loadouts = Loadouts::open()?;
+        let recall = RecallResults::open()?;
+        let settings = Settings::open()?;
+        // A broken rules file must not lock the player out, subcommands included
+        let (rules, rules_error) = match Rules::open() {
+            Ok(rules) => (rules, None),
+            Err(err) => {
+                let err = format!("{err}, playing with the default rules");
+                eprintln!("{err}");
+                (Rules::default(), Some(err))
+            }
+        };
 
         Ok(Self {
             screen: Default::default(),
             player,
-            leaderboard,
+            records,
+            seed: None,
             is_running: true,
             upgrades,
+            loadouts,
+            loadout: Vec::new(),
+            recall,
+            settings,
+            rules,
+            duration: None,
+            rules_error,
         })
     }
 
     pub fn handle_args(&mut self) -> Result<()> {
-        if let Some(arg) = std::env::args().nth(1) {
+        if let Some(seed) = crate::utility::arg_value("seed") {
+            self.seed = Some(seed.parse().map_err(|_| format!("Invalid seed '{seed}'"))?);
+        }
+
+        if let Some(secs) = crate::utility::arg_value("duration") {
+            match secs.parse() {
+                Ok(secs) if secs > 0 => self.duration = Some(Duration::from_secs(secs)),
+                _ => return Err(format!("Invalid duration '{secs}'").into()),
+            }
+        }
+
+        if let Some(arg) = std::env::args().nth(1).filter(|arg| !arg.starts_with("--")) {
             if arg.eq("leaderboard") {
-                self.leaderboard
+                self.records
+                    .leaderboard
                     .sorted_vec()
                     .iter()
                     .enumerate()
@@ -90,6 +214,8 @@ impl App {
                 }
                 println!("Deleted all game-related data successfully");
                 return Ok(());
+            } else if arg.eq("simulate") {
+                self.simulate()?;
             }
 
             self.is_running = false;
@@ -98,7 +224,109 @@ impl App {
         Ok(())
     }
 
+    /// Scores a replay or a script of timed inputs without a terminal. Scripts are played
+    /// with the default settings unless flags say otherwise, never with the player's own
+    fn simulate(&self) -> Result<()> {
+        const USAGE: &str = "Usage: strategem-hero simulate <replay or inputs file> [--seed <number>] \
+            [--mode <classic|endless|hardcore|sprint-<target>>] [--difficulty <name>] [--duration <seconds>]";
+
+        let path = std::env::args().nth(2).ok_or(USAGE)?;
+        let path = Path::new(&path);
+        let (replay, inputs) = if path.extension().is_some_and(|ext| ext == "replay") {
+            let replay = Replay::load(path)?;
+            let inputs = replay.inputs.clone();
+            (Some(replay), inputs)
+        } else {
+            (None, parse_script(&std::fs::read_to_string(path)?)?)
+        };
+
+        let recorded = replay.as_ref().map(|replay| replay.score);
+        let mut player = replay
+            .as_ref()
+            .map_or_else(PlayerData::default, Replay::player);
+        let mut records = Records::default();
+        let builder = Game::builder(&mut player, &mut records);
+        let mut game = match replay {
+            Some(replay) => builder.playback(replay),
+            None => builder
+                .timer(GameTimer::start_from(
+                    self.duration.unwrap_or(DEFAULT_DURATION),
+                ))
+                .mode(Self::simulated_mode()?)
+                .difficulty(Self::simulated_difficulty()?)
+                .seed(Some(self.seed.ok_or("A script needs a --seed")?)),
+        }
+        .build();
+        game.simulate(&inputs);
+
+        println!("Seed: {}", game.seed());
+        println!("Difficulty: {}", game.difficulty());
+        println!("Modifiers: {}", game.modifiers());
+        println!("  #  {:<36} {:>8} {:>9}", "Strategem", "Time", "Mistakes");
+        game.results().iter().enumerate().for_each(|(i, result)| {
+            println!(
+                "{:>3}. {:<36} {:>8} {:>9}",
+                i + 1,
+                result.name,
+                format_duration(result.time),
+                result.mistakes
+            )
+        });
+
+        let summary = game.summary();
+        println!("Cleared:     {}", game.cleared());
+        println!("Best streak: {}", summary.best_streak);
+        println!("Accuracy:    {:.1}%", summary.accuracy);
+        println!("Final score: {}", summary.score);
+        if let Some(recorded) = recorded {
+            match recorded == summary.score {
+                true => println!("Matches the recorded score"),
+                false => println!("Recorded score was {recorded}, the replay does not match"),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Mode given with `--mode` to simulate a script in, classic by default
+    fn simulated_mode() -> Result<GameMode> {
+        let Some(name) = crate::utility::arg_value("mode") else {
+            return Ok(GameMode::default());
+        };
+
+        match name.to_lowercase().as_str() {
+            "classic" => Ok(GameMode::Classic),
+            "endless" => Ok(GameMode::Endless),
+            "hardcore" => Ok(GameMode::Hardcore),
+            other => other
+                .strip_prefix("sprint-")
+                .and_then(|target| target.parse().ok())
+                .filter(|target| *target > 0)
+                .map(GameMode::Sprint)
+                .ok_or_else(|| format!("Invalid mode '{name}'").into()),
+        }
+    }
+
+    /// Difficulty given with `--difficulty` to simulate a script on, e.g. `suicide-mission`
+    fn simulated_difficulty() -> Result<Difficulty> {
+        let Some(name) = crate::utility::arg_value("difficulty") else {
+            return Ok(Difficulty::default());
+        };
+
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| {
+                difficulty.to_string().to_lowercase().replace(' ', "-") == name.to_lowercase()
+            })
+            .ok_or_else(|| format!("Invalid difficulty '{name}'").into())
+    }
+
     pub fn run(mut self) -> Result<()> {
+        // Subcommands finish before the terminal is touched
+        if !self.is_running {
+            return Ok(());
+        }
+
         let _guard = crate::tui::HideCursor::hide()?;
 
         crossterm::terminal::enable_raw_mode()?;
@@ -112,9 +340,22 @@ impl App {
 
             match self.screen {
                 Screen::Main => self.render_main()?,
-                Screen::Game => self.render_game()?,
+                Screen::Demo => self.render_demo()?,
+                Screen::Modes => self.render_modes()?,
+                Screen::Game(mode) => self.render_game(mode)?,
+                Screen::HotSeat => self.render_hot_seat()?,
+                Screen::Versus => self.render_versus()?,
+                Screen::Lan => self.render_lan()?,
+                Screen::GhostRace => self.render_ghost_race()?,
+                Screen::Training => self.render_training()?,
+                Screen::Practice => self.render_practice()?,
+                Screen::Quiz => self.render_quiz()?,
+                Screen::Recall => self.render_recall()?,
+                Screen::Loadouts => self.render_loadouts()?,
                 Screen::Leaderboard => self.render_leaderboard()?,
+                Screen::Replays => self.render_replays()?,
                 Screen::Upgrades => self.render_upgrades()?,
+                Screen::Settings => self.render_settings()?,
                 Screen::DeleteData => self.render_delete_data()?,
             }
         }
@@ -125,64 +366,601 @@ impl App {
 
     fn render_main(&mut self) -> Result<()> {
         screenln!("{LOGO}")?;
+        if let Some(err) = &self.rules_error {
+            screenln!("{}", err.as_str().red())?;
+        }
         match crate::tui::menu::Menu::builder()
             .add_item("Start Game")
+            .add_item("Training")
+            .add_item("Loadouts")
             .add_item("Leaderboard")
+            .add_item("Replays")
             .add_item("Upgrades")
+            .add_item("Settings")
             .add_item("Delete Data")
             .add_item("Quit")
             .build()
-            .exec("Main Menu")?
+            .exec_until_idle("Main Menu", ATTRACT_DELAY)?
         {
-            Some(0) => self.screen.set_game(),
-            Some(1) => self.screen.set_leaderboard(),
-            Some(2) => self.screen.set_upgrades(),
-            Some(3) => self.screen.set_delete_data(),
+            Selection::Item(0) => self.screen.set_modes(),
+            Selection::Item(1) => self.screen.set_training(),
+            Selection::Item(2) => self.screen.set_loadouts(),
+            Selection::Item(3) => self.screen.set_leaderboard(),
+            Selection::Item(4) => self.screen.set_replays(),
+            Selection::Item(5) => self.screen.set_upgrades(),
+            Selection::Item(6) => self.screen.set_settings(),
+            Selection::Item(7) => self.screen.set_delete_data(),
+            Selection::Idle => self.screen.set_demo(),
             _ => self.is_running = false,
         }
 
         Ok(())
     }
 
-    fn render_game(&mut self) -> Result<()> {
-        let secs = if cfg!(debug_assertions) {
-            Duration::from_secs(10)
-        } else {
-            Duration::from_secs(30)
+    /// Bot played run shown while nobody is at the main menu
+    fn render_demo(&mut self) -> Result<()> {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let mut game = Game::builder(&mut player, &mut records)
+            .timer(self.game_timer())
+            // Time rewards decay in endless runs, so even the bot runs out of time
+            .mode(GameMode::Endless)
+            .rules(self.rules.clone())
+            .input(Bot::new(rand::random()))
+            .demo()
+            .build();
+
+        let _sc = crate::tui::screen::cleaner();
+        game.run()?;
+        self.screen.set_main();
+
+        Ok(())
+    }
+
+    fn render_modes(&mut self) -> Result<()> {
+        let today = crate::utility::today();
+
+        screenln!("{LOGO}")?;
+        match crate::tui::menu::Menu::builder()
+            .add_item("Classic".to_string())
+            .add_item("Endless".to_string())
+            .add_item(format!("Sprint ({} strategems)", SPRINT_LENGTHS[0]))
+            .add_item(format!("Sprint ({} strategems)", SPRINT_LENGTHS[1]))
+            .add_item(format!("Daily Challenge ({})", format_day(today)))
+            .add_item("Hardcore".to_string())
+            .add_item("Memory".to_string())
+            .add_item("Hot-Seat (2 players)".to_string())
+            .add_item("Versus (split keyboard)".to_string())
+            .add_item("LAN Versus".to_string())
+            .add_item("Ghost Race".to_string())
+            .build()
+            .exec(&format!("Game Mode ({})", self.settings.difficulty()))?
+        {
+            Some(0) => self.screen.set_game(GameMode::Classic),
+            Some(1) => self.screen.set_game(GameMode::Endless),
+            Some(idx @ 2..=3) => self
+                .screen
+                .set_game(GameMode::Sprint(SPRINT_LENGTHS[idx - 2])),
+            Some(4) => self.screen.set_game(GameMode::Daily(today)),
+            Some(5) => self.screen.set_game(GameMode::Hardcore),
+            Some(6) => {
+                const PREVIEWS: [u64; 4] = [500, 1000, 2000, 3000];
+
+                let mut menu = crate::tui::menu::Menu::builder();
+                for millis in PREVIEWS {
+                    menu = menu.add_item(format_duration(Duration::from_millis(millis)));
+                }
+
+                let _sc = crate::tui::screen::cleaner();
+                crate::tui::screen::full_clear()?;
+                if let Some(idx) = menu.build().exec("Code Preview Time")? {
+                    let preview = Duration::from_millis(PREVIEWS[idx]);
+                    self.screen.set_game(GameMode::Memory(preview));
+                }
+            }
+            Some(7) => self.screen.set_hot_seat(),
+            Some(8) => self.screen.set_versus(),
+            Some(9) => self.screen.set_lan(),
+            Some(10) => self.screen.set_ghost_race(),
+            _ => self.screen.set_main(),
+        }
+
+        Ok(())
+    }
+
+    fn render_game(&mut self, mode: GameMode) -> Result<()> {
+        let pool = match mode {
+            GameMode::Loadout => self.loadout.clone(),
+            _ => Vec::new(),
         };
-        let game_timer = GameTimer::start_from(secs);
-        let penalty = InputFreeze::new(30);
-        let controls = if std::env::args().any(|arg| arg.eq("--wasd")) {
-            Controls::wasd()
-        } else {
-            Controls::arrows()
+        let timer = self.game_timer();
+        let mut game = Game::builder(&mut self.player, &mut self.records)
+            .timer(timer)
+            .controls(Self::controls())
+            .difficulty(self.settings.difficulty())
+            .modifiers(self.settings.modifiers().clone())
+            .rules(self.rules.clone())
+            .mode(mode)
+            .seed(self.seed)
+            .pool(pool)
+            .build();
+
+        if !game.run()? {
+            self.screen.set_main();
+        }
+
+        Ok(())
+    }
+
+    fn render_hot_seat(&mut self) -> Result<()> {
+        self.screen.set_modes();
+
+        screenln!("{LOGO}")?;
+        screenln!("Hot-Seat: two players take turns on the same run")?;
+        let Some(first) = crate::tui::input("Player 1 name")? else {
+            return Ok(());
+        };
+        let Some(second) = crate::tui::input("Player 2 name")? else {
+            return Ok(());
+        };
+
+        // Both players get the same strategem order
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let mut results = Vec::new();
+
+        for name in [&first, &second] {
+            let _sc = crate::tui::screen::cleaner();
+            crate::tui::screen::full_clear()?;
+            screenln!("{name}, get ready!")?;
+            crate::tui::confirm_quit(Some("start"))?;
+
+            let timer = self.game_timer();
+            let mut game = Game::builder(&mut self.player, &mut self.records)
+                .timer(timer)
+                .controls(Self::controls())
+                .difficulty(self.settings.difficulty())
+                .modifiers(self.settings.modifiers().clone())
+                .rules(self.rules.clone())
+                .seed(Some(seed))
+                .name(name.as_str())
+                .single_run()
+                .build();
+            game.run()?;
+
+            if !game.is_over() {
+                return Ok(());
+            }
+            results.push(game.summary());
+        }
+
+        let _sc = crate::tui::screen::cleaner();
+        crate::tui::screen::full_clear()?;
+
+        let (a, b) = (&results[0], &results[1]);
+        screenln!("Hot-Seat results")?;
+        screenln!("  {:<12} {:>18} {:>18}", "", first, second)?;
+        screenln!("  {:<12} {:>18} {:>18}", "Score", a.score, b.score)?;
+        screenln!(
+            "  {:<12} {:>18} {:>18}",
+            "Best streak",
+            a.best_streak,
+            b.best_streak
+        )?;
+        screenln!(
+            "  {:<12} {:>17.1}% {:>17.1}%",
+            "Accuracy",
+            a.accuracy,
+            b.accuracy
+        )?;
+        match a.score.cmp(&b.score) {
+            std::cmp::Ordering::Greater => screenln!("{first} wins!")?,
+            std::cmp::Ordering::Less => screenln!("{second} wins!")?,
+            std::cmp::Ordering::Equal => screenln!("It's a draw!")?,
+        }
+
+        crate::tui::confirm_quit(Some("return to game modes"))
+    }
+
+    fn render_versus(&mut self) -> Result<()> {
+        let seed = self.seed.unwrap_or_else(rand::random);
+        Versus::new(seed, self.rules.clone()).run()?;
+        self.screen.set_modes();
+        Ok(())
+    }
+
+    fn render_lan(&mut self) -> Result<()> {
+        screenln!("{LOGO}")?;
+        let choice = crate::tui::menu::Menu::builder()
+            .add_item("Host")
+            .add_item("Join")
+            .build()
+            .exec("LAN Versus")?;
+
+        let _sc = crate::tui::screen::cleaner();
+        crate::tui::screen::full_clear()?;
+
+        let race = match choice {
+            Some(0) => {
+                let port = match crate::utility::arg_value("port") {
+                    Some(port) => port.parse().map_err(|_| format!("Invalid port '{port}'"))?,
+                    None => DEFAULT_PORT,
+                };
+                let seed = self.seed.unwrap_or_else(rand::random);
+                LanVersus::host(port, seed, Self::controls(), self.rules.clone())
+            }
+            Some(1) => match crate::tui::input("Host address")? {
+                Some(addr) => LanVersus::join(&addr, Self::controls(), self.rules.clone()),
+                None => Ok(None),
+            },
+            _ => {
+                self.screen.set_modes();
+                return Ok(());
+            }
         };
-        let mut game = Game::new(
-            &mut self.player,
-            &mut self.leaderboard,
-            game_timer,
-            controls,
-            penalty,
-        );
 
+        // Network errors are expected, they lead back to the menu instead of quitting
+        match race {
+            Ok(Some(mut race)) => race.run()?,
+            Ok(None) => return Ok(()),
+            Err(err) => {
+                screenln!("{err}")?;
+                crate::tui::confirm_quit(Some("return to game modes"))?;
+            }
+        }
+        self.screen.set_modes();
+
+        Ok(())
+    }
+
+    fn render_ghost_race(&mut self) -> Result<()> {
+        let bests = Ghost::personal_bests()?;
+
+        screenln!("{LOGO}")?;
+        if bests.is_empty() {
+            screenln!("Finish a run first, ghosts are built from recorded runs")?;
+            crate::tui::confirm_quit(Some("return to game modes"))?;
+            self.screen.set_modes();
+            return Ok(());
+        }
+
+        let mut menu = crate::tui::menu::Menu::builder();
+        for replay in bests.iter() {
+            menu = menu.add_item(format!(
+                "{:<16} best {}",
+                replay.mode.to_string(),
+                replay.score
+            ));
+        }
+
+        let Some(idx) = menu.build().exec("Race Against Your Best")? else {
+            self.screen.set_modes();
+            return Ok(());
+        };
+
+        let replay = bests.into_iter().nth(idx).ok_or("Replay not found")?;
+        let mut game = Game::builder(&mut self.player, &mut self.records)
+            .controls(Self::controls())
+            .ghost(replay)
+            .build();
+
+        let _sc = crate::tui::screen::cleaner();
         if !game.run()? {
+            self.screen.set_modes();
+        }
+
+        Ok(())
+    }
+
+    fn render_training(&mut self) -> Result<()> {
+        screenln!("{LOGO}")?;
+        match crate::tui::menu::Menu::builder()
+            .add_item("Practice")
+            .add_item("Quiz")
+            .add_item("Recall")
+            .build()
+            .exec("Training")?
+        {
+            Some(0) => self.screen.set_practice(),
+            Some(1) => self.screen.set_quiz(),
+            Some(2) => self.screen.set_recall(),
+            _ => self.screen.set_main(),
+        }
+
+        Ok(())
+    }
+
+    fn render_quiz(&mut self) -> Result<()> {
+        Quiz::new(10).run()?;
+        self.screen.set_training();
+        Ok(())
+    }
+
+    fn render_recall(&mut self) -> Result<()> {
+        Recall::new(&mut self.recall, Self::controls()).run()?;
+        self.screen.set_training();
+        Ok(())
+    }
+
+    fn render_practice(&mut self) -> Result<()> {
+        let mut menu = crate::tui::menu::Menu::builder();
+        for class in StrategemClass::ALL.iter() {
+            menu = menu.add_item(class);
+        }
+
+        screenln!("{LOGO}")?;
+        let Some(class) = menu
+            .build()
+            .exec("Practice")?
+            .map(|idx| &StrategemClass::ALL[idx])
+        else {
+            self.screen.set_training();
+            return Ok(());
+        };
+
+        let class_pool = ALL_STRATEGEMS
+            .iter()
+            .filter(|s| s.class().eq(class))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let _sc = crate::tui::screen::cleaner();
+        crate::tui::screen::full_clear()?;
+
+        let mut menu =
+            crate::tui::menu::Menu::builder().add_item(format!("All {class} strategems"));
+        for strategem in class_pool.iter() {
+            menu = menu.add_item(strategem.name().to_string());
+        }
+
+        let mut practice = match menu.build().exec(&format!("{class} strategems"))? {
+            Some(0) => Practice::new(class.to_string(), class_pool, Self::controls()),
+            Some(idx) => {
+                let strategem = class_pool[idx - 1].clone();
+                Practice::new(strategem.name(), vec![strategem], Self::controls())
+            }
+            None => return Ok(()),
+        };
+
+        practice.run()
+    }
+
+    fn render_loadouts(&mut self) -> Result<()> {
+        let mut menu = crate::tui::menu::Menu::builder();
+        for (name, strategems) in self.loadouts.iter() {
+            menu = menu.add_item(format!("{:<18} {}", name, strategems.join(", ")));
+        }
+
+        screenln!("{LOGO}")?;
+        let Some(idx) = menu
+            .add_item("Create Loadout".to_string())
+            .build()
+            .exec("Loadouts")?
+        else {
             self.screen.set_main();
+            return Ok(());
+        };
+
+        let Some((name, strategems)) = self
+            .loadouts
+            .iter()
+            .nth(idx)
+            .map(|(name, strategems)| (name.clone(), strategems.clone()))
+        else {
+            return self.create_loadout();
+        };
+
+        let _sc = crate::tui::screen::cleaner();
+        crate::tui::screen::full_clear()?;
+
+        screenln!("Strategems: {}", strategems.join(", "))?;
+        match crate::tui::menu::Menu::builder()
+            .add_item("Play")
+            .add_item("Delete")
+            .build()
+            .exec(&name)?
+        {
+            Some(0) => {
+                self.loadout = strategems
+                    .iter()
+                    .filter_map(|name| crate::strategem::find(name))
+                    .collect();
+                self.screen.set_game(GameMode::Loadout);
+            }
+            Some(1) => {
+                self.loadouts.remove(&name);
+                self.loadouts.save()?;
+            }
+            _ => (),
         }
 
         Ok(())
     }
 
+    fn create_loadout(&mut self) -> Result<()> {
+        let mut picked: Vec<Strategem> = Vec::new();
+
+        loop {
+            let _sc = crate::tui::screen::cleaner();
+            crate::tui::screen::full_clear()?;
+
+            let regular = picked
+                .iter()
+                .filter(|s| s.class().ne(&StrategemClass::Mission))
+                .count();
+            let mut menu = crate::tui::menu::Menu::builder()
+                .add_item(format!(
+                    "Add strategem ({regular}/{})",
+                    Loadouts::MAX_STRATEGEMS
+                ))
+                .add_item("Add mission strategem".to_string())
+                .add_item("Save loadout".to_string());
+            for strategem in picked.iter() {
+                menu = menu.add_item(format!("Remove {}", strategem.name()));
+            }
+
+            match menu.build().exec("New Loadout")? {
+                Some(0) if regular < Loadouts::MAX_STRATEGEMS => {
+                    if let Some(strategem) = Self::pick_strategem(false)? {
+                        if picked.iter().all(|s| s.name().ne(strategem.name())) {
+                            picked.push(strategem);
+                        }
+                    }
+                }
+                Some(1) => {
+                    if let Some(strategem) = Self::pick_strategem(true)? {
+                        if picked.iter().all(|s| s.name().ne(strategem.name())) {
+                            picked.push(strategem);
+                        }
+                    }
+                }
+                Some(2) if !picked.is_empty() => {
+                    if let Some(name) = crate::tui::input("Loadout name")? {
+                        let names = picked.iter().map(|s| s.name().to_string()).collect();
+                        self.loadouts.insert(&name, names);
+                        return self.loadouts.save();
+                    }
+                }
+                Some(idx @ 3..) => {
+                    picked.remove(idx - 3);
+                }
+                None => return Ok(()),
+                _ => (),
+            }
+        }
+    }
+
+    fn pick_strategem(mission: bool) -> Result<Option<Strategem>> {
+        let _sc = crate::tui::screen::cleaner();
+        crate::tui::screen::full_clear()?;
+
+        let class = if mission {
+            &StrategemClass::Mission
+        } else {
+            let classes = StrategemClass::ALL
+                .iter()
+                .filter(|class| class.ne(&&StrategemClass::Mission))
+                .collect::<Vec<_>>();
+            let mut menu = crate::tui::menu::Menu::builder();
+            for class in classes.iter() {
+                menu = menu.add_item(class);
+            }
+
+            let Some(idx) = menu.build().exec("Strategem Class")? else {
+                return Ok(None);
+            };
+            crate::tui::screen::full_clear()?;
+            classes[idx]
+        };
+
+        let pool = ALL_STRATEGEMS
+            .iter()
+            .filter(|s| s.class().eq(class))
+            .collect::<Vec<_>>();
+        let mut menu = crate::tui::menu::Menu::builder();
+        for strategem in pool.iter() {
+            menu = menu.add_item(strategem.name());
+        }
+
+        Ok(menu
+            .build()
+            .exec(&format!("{class} strategems"))?
+            .map(|idx| pool[idx].clone()))
+    }
+
     fn render_leaderboard(&mut self) -> Result<()> {
+        let names = self.records.boards.names().cloned().collect::<Vec<_>>();
+        let mut menu = crate::tui::menu::Menu::builder().add_item("Classic".to_string());
+        for name in names.iter() {
+            menu = menu.add_item(name.clone());
+        }
+        for target in SPRINT_LENGTHS {
+            menu = menu.add_item(format!("Sprint {target}"));
+        }
+        menu = menu.add_item("Daily Challenge".to_string());
+
         screenln!("{LOGO}")?;
-        screenln!("----[ Leaderboard ]----------")?;
-        self.leaderboard
-            .sorted_vec()
+        let Some(idx) = menu.build().exec("Leaderboards")? else {
+            self.screen.set_main();
+            return Ok(());
+        };
+
+        let _sc = crate::tui::screen::cleaner();
+        crate::tui::screen::full_clear()?;
+
+        let sprint_idx = names.len() + 1;
+        if idx < sprint_idx {
+            let (title, leaderboard) = match idx.checked_sub(1) {
+                Some(i) => (names[i].as_str(), self.records.boards.get(&names[i])),
+                None => ("Leaderboard", Some(&self.records.leaderboard)),
+            };
+
+            screenln!("----[ {title} ]----------")?;
+            leaderboard
+                .map(|leaderboard| leaderboard.sorted_vec())
+                .unwrap_or_default()
+                .iter()
+                .enumerate()
+                .for_each(|(i, rec)| screenln!("  {}. {:<18} {}", i + 1, rec.0, rec.1).unwrap());
+        } else if let Some(target) = SPRINT_LENGTHS.get(idx - sprint_idx) {
+            screenln!("----[ Sprint {target} ]----------")?;
+            self.records
+                .sprint
+                .sorted_vec(*target)
+                .iter()
+                .enumerate()
+                .for_each(|(i, rec)| {
+                    let time = format_duration(Duration::from_millis(*rec.1));
+                    screenln!("  {}. {:<18} {}", i + 1, rec.0, time).unwrap()
+                });
+        } else {
+            screenln!("----[ Daily Challenge ]----------")?;
+            self.records
+                .daily
+                .recent(5)
+                .iter()
+                .for_each(|(day, score)| screenln!("  {}  {}", format_day(*day), score).unwrap());
+        }
+
+        crate::tui::confirm_quit(Some("return to leaderboards"))
+    }
+
+    fn render_replays(&mut self) -> Result<()> {
+        const MAX_LISTED: usize = 15;
+
+        let replays = Replay::list()?
             .iter()
-            .enumerate()
-            .for_each(|(i, rec)| screenln!("  {}. {:<18} {}", i + 1, rec.0, rec.1).unwrap());
+            .take(MAX_LISTED)
+            .filter_map(|path| Replay::load(path).ok())
+            .collect::<Vec<_>>();
 
-        crate::tui::confirm_quit(Some("return to main menu"))?;
-        self.screen.set_main();
+        screenln!("{LOGO}")?;
+        if replays.is_empty() {
+            screenln!("No replays recorded yet")?;
+            crate::tui::confirm_quit(Some("return to main menu"))?;
+            self.screen.set_main();
+            return Ok(());
+        }
+
+        let mut menu = crate::tui::menu::Menu::builder();
+        for replay in replays.iter() {
+            menu = menu.add_item(replay);
+        }
+
+        let Some(idx) = menu.build().exec("Replays")? else {
+            self.screen.set_main();
+            return Ok(());
+        };
+
+        let replay = replays.into_iter().nth(idx).ok_or("Replay not found")?;
+        let mut player = replay.player();
+        let mut records = Records::default();
+        let mut game = Game::builder(&mut player, &mut records)
+            .controls(Self::controls())
+            .playback(replay)
+            .build();
+
+        let _sc = crate::tui::screen::cleaner();
+        game.run()?;
 
         Ok(())
     }
@@ -223,10 +1001,83 @@ impl App {
         self.upgrades.save()
     }
 
+    fn render_settings(&mut self) -> Result<()> {
+        screenln!("{LOGO}")?;
+        let length = format!("Game length: {}s", self.settings.duration().as_secs());
+        let difficulty = format!("Difficulty: {}", self.settings.difficulty());
+        let modifiers = match self.settings.modifiers().is_random() {
+            true => String::from("Modifiers: Random every run"),
+            false => format!("Modifiers: {}", self.settings.modifiers()),
+        };
+        let setting = crate::tui::menu::Menu::builder()
+            .add_item(length)
+            .add_item(difficulty)
+            .add_item(modifiers)
+            .build()
+            .exec("Settings")?;
+
+        let _sc = crate::tui::screen::cleaner();
+        crate::tui::screen::full_clear()?;
+        match setting {
+            Some(0) => {
+                let mut menu = crate::tui::menu::Menu::builder();
+                for secs in Settings::DURATIONS {
+                    menu = menu.add_item(format!("{secs} seconds"));
+                }
+
+                if let Some(idx) = menu.build().exec("Game Length")? {
+                    self.settings.set_duration(Settings::DURATIONS[idx]);
+                }
+            }
+            Some(1) => {
+                let mut menu = crate::tui::menu::Menu::builder();
+                for difficulty in Difficulty::ALL {
+                    menu = menu.add_item(difficulty.describe());
+                }
+
+                if let Some(idx) = menu.build().exec("Difficulty")? {
+                    self.settings.set_difficulty(Difficulty::ALL[idx]);
+                }
+            }
+            Some(2) => loop {
+                let _sc = crate::tui::screen::cleaner();
+                let modifiers = self.settings.modifiers();
+                let mut menu = crate::tui::menu::Menu::builder().add_item(format!(
+                    "[{}] Random every run",
+                    if modifiers.is_random() { 'x' } else { ' ' }
+                ));
+                for modifier in Modifier::ALL {
+                    let on = if modifiers.has(modifier) { 'x' } else { ' ' };
+                    menu = menu.add_item(format!("[{on}] {}", modifier.describe()));
+                }
+
+                match menu.build().exec("Modifiers")? {
+                    Some(0) if modifiers.is_random() => {
+                        *self.settings.modifiers_mut() = Modifiers::default()
+                    }
+                    Some(0) => *self.settings.modifiers_mut() = Modifiers::random(),
+                    Some(idx) => self.settings.modifiers_mut().toggle(Modifier::ALL[idx - 1]),
+                    None => break,
+                }
+            },
+            _ => {
+                self.screen.set_main();
+                return Ok(());
+            }
+        }
+
+        self.settings.save()
+    }
+
     fn render_delete_data(&mut self) -> Result<()> {
         let datadir = crate::utility::data_dir()?;
         for entry in std::fs::read_dir(datadir)? {
-            std::fs::remove_file(entry?.path())?;
+            let path = entry?.path();
+            if path.is_dir() {
+                std::fs::remove_dir_all(path)?;
+            } else {
+                std::fs::remove_file(path)?;
+            }
         }
 
         screenln!("Deleted all game-related data successfully. Relaunch is required")?;
@@ -235,6 +1086,18 @@ impl App {
         Ok(())
     }
 
+    fn game_timer(&self) -> GameTimer {
+        GameTimer::start_from(self.duration.unwrap_or(self.settings.duration()))
+    }
+
+    fn controls() -> Controls {
+        if std::env::args().any(|arg| arg.eq("--wasd")) {
+            Controls::wasd()
+        } else {
+            Controls::arrows()
+        }
+    }
+
     fn purchase_upgrade(&mut self, idx: usize) -> Result<bool> {
         let upgrade = &mut self.upgrades[idx];
         if upgrade.is_purchased() {
@@ -2,8 +2,11 @@ use std::fmt::Display;
 
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
 
+use serde::{Deserialize, Serialize};
+
 use crate::error::Result;
 
+#[derive(Clone)]
 pub struct Controls {
     up: KeyCode,
     down: KeyCode,
@@ -29,6 +32,16 @@ impl Controls {
             right: KeyCode::Right,
         }
     }
+
+    fn map(&self, code: &KeyCode) -> Option<Key> {
+        match code {
+            code if code.eq(&self.up) => Some(Key::ArrowUp),
+            code if code.eq(&self.down) => Some(Key::ArrowDown),
+            code if code.eq(&self.left) => Some(Key::ArrowLeft),
+            code if code.eq(&self.right) => Some(Key::ArrowRight),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Controls {
@@ -48,7 +61,7 @@ impl Display for Controls {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Key {
     ArrowUp,
     ArrowDown,
@@ -88,6 +101,36 @@ pub fn read(controls: &Controls) -> Result<Option<Key>> {
     }
 }
 
+/// Key press attributed to one of several players sharing the keyboard
+#[derive(Debug, PartialEq)]
+pub enum RoutedKey {
+    Player(usize, Key),
+    Escape,
+}
+
+/// Routes `code` to the first player whose controls contain it
+pub fn route(controls: &[Controls], code: &KeyCode) -> Option<RoutedKey> {
+    if code.eq(&KeyCode::Esc) {
+        return Some(RoutedKey::Escape);
+    }
+
+    controls
+        .iter()
+        .enumerate()
+        .find_map(|(player, controls)| Some(RoutedKey::Player(player, controls.map(code)?)))
+}
+
+pub fn read_routed(controls: &[Controls]) -> Result<Option<RoutedKey>> {
+    match crossterm::event::read()? {
+        Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) => Ok(route(controls, &code)),
+        _ => Ok(None),
+    }
+}
+
 fn format_key_code(code: &KeyCode) -> String {
     match code {
         KeyCode::Char(ch) => format!("'{ch}'"),
@@ -98,3 +141,24 @@ fn format_key_code(code: &KeyCode) -> String {
         other => format!("{other:?}"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_routed_by_controls() {
+        let controls = [Controls::wasd(), Controls::arrows()];
+
+        assert_eq!(
+            Some(RoutedKey::Player(0, Key::ArrowUp)),
+            route(&controls, &KeyCode::Char('w'))
+        );
+        assert_eq!(
+            Some(RoutedKey::Player(1, Key::ArrowLeft)),
+            route(&controls, &KeyCode::Left)
+        );
+        assert_eq!(Some(RoutedKey::Escape), route(&controls, &KeyCode::Esc));
+        assert_eq!(None, route(&controls, &KeyCode::Char('x')));
+    }
+}
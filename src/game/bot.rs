@@ -0,0 +1,128 @@
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::input::{self, InputSource, FRAME};
+use crate::{
+    error::Result,
+    event::Key,
+    strategem::{Strategem, StrategemKey},
+};
+
+/// Milliseconds taken to read a newly shown code before the first key
+const REACTION_MS: Range<u64> = 350..700;
+
+/// Milliseconds between two keys of the same code
+const KEY_DELAY_MS: Range<u64> = 110..240;
+
+const MISTAKE_CHANCE: f64 = 0.04;
+
+const KEYS: [StrategemKey; 4] = [
+    StrategemKey::Up,
+    StrategemKey::Down,
+    StrategemKey::Left,
+    StrategemKey::Right,
+];
+
+/// Computer player typing the codes it is shown at a human pace, with the odd mistake,
+/// any key pressed at the keyboard stops it
+pub struct Bot {
+    rng: StdRng,
+    expected: Option<StrategemKey>,
+    /// When the next key is due, `None` until a code to type was shown
+    due: Option<Duration>,
+}
+
+impl Bot {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            expected: None,
+            due: None,
+        }
+    }
+
+    /// Key to press at `now`, if one is due
+    fn press(&mut self, now: Duration) -> Option<Key> {
+        let expected = self.expected.clone()?;
+        let due = *self
+            .due
+            .get_or_insert_with(|| now + Duration::from_millis(self.rng.gen_range(REACTION_MS)));
+        if now < due {
+            return None;
+        }
+
+        self.due = Some(now + Duration::from_millis(self.rng.gen_range(KEY_DELAY_MS)));
+        // Waits to be shown the code again before pressing the next key
+        self.expected = None;
+
+        if self.rng.gen_bool(MISTAKE_CHANCE) {
+            let wrong: Vec<_> = KEYS.iter().filter(|key| **key != expected).collect();
+            return wrong.choose(&mut self.rng).map(|key| (*key).clone().into());
+        }
+
+        Some(expected.into())
+    }
+}
+
+impl InputSource for Bot {
+    fn next(&mut self, started_at: Instant) -> Result<Option<(Duration, Key)>> {
+        if input::key_pressed()? {
+            return Ok(Some((started_at.elapsed(), Key::Escape)));
+        }
+
+        let now = started_at.elapsed();
+        if let Some(key) = self.press(now) {
+            return Ok(Some((now, key)));
+        }
+
+        std::thread::sleep(FRAME);
+        Ok(None)
+    }
+
+    fn observe(&mut self, strategem: &Strategem) {
+        self.expected = strategem.next_key();
+        // A new code, or one reset after a mistake, has to be read first
+        if self.expected.is_none() {
+            self.due = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategem::StrategemSource;
+
+    #[test]
+    fn bot_clears_codes_at_human_pace() {
+        let mut bot = Bot::new(42);
+        let mut source = StrategemSource::new(42);
+
+        for _ in 0..20 {
+            let mut strategem = source.next();
+            let mut now = Duration::ZERO;
+
+            while !strategem.is_completed() {
+                if !strategem.is_valid() {
+                    strategem.reset();
+                }
+
+                bot.observe(&strategem);
+                if let Some(key) = bot.press(now) {
+                    strategem.assert_key(key.into());
+                }
+                bot.observe(&strategem);
+                now += FRAME;
+            }
+
+            let fastest =
+                REACTION_MS.start + KEY_DELAY_MS.start * (strategem.code_len() as u64 - 1);
+            assert!(now >= Duration::from_millis(fastest));
+            bot.observe(&strategem);
+        }
+    }
+}
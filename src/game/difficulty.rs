@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utility::InputFreeze;
+
+/// Named preset a run is played on, scaling the timer and the score
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Trivial,
+    Easy,
+    #[default]
+    Medium,
+    Challenging,
+    Hard,
+    Extreme,
+    SuicideMission,
+    Impossible,
+    Helldive,
+}
+
+/// Values of a preset, in percent of the medium ones unless noted otherwise
+struct Preset {
+    starting_time: u32,
+    time_reward: u32,
+    /// Frames the input stays frozen after a wrong key
+    freeze_frames: u32,
+    penalty: u32,
+    score: u32,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 9] = [
+        Difficulty::Trivial,
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Challenging,
+        Difficulty::Hard,
+        Difficulty::Extreme,
+        Difficulty::SuicideMission,
+        Difficulty::Impossible,
+        Difficulty::Helldive,
+    ];
+
+    const fn preset(&self) -> Preset {
+        let (starting_time, time_reward, freeze_frames, penalty, score) = match self {
+            Difficulty::Trivial => (150, 150, 15, 25, 50),
+            Difficulty::Easy => (130, 130, 20, 50, 75),
+            Difficulty::Medium => (100, 100, 30, 100, 100),
+            Difficulty::Challenging => (90, 90, 35, 125, 125),
+            Difficulty::Hard => (80, 80, 40, 150, 150),
+            Difficulty::Extreme => (70, 70, 45, 175, 175),
+            Difficulty::SuicideMission => (60, 60, 50, 200, 200),
+            Difficulty::Impossible => (50, 50, 55, 250, 250),
+            Difficulty::Helldive => (40, 40, 60, 300, 300),
+        };
+
+        Preset {
+            starting_time,
+            time_reward,
+            freeze_frames,
+            penalty,
+            score,
+        }
+    }
+
+    /// Time a run of the chosen `length` starts with
+    pub fn starting_time(&self, length: Duration) -> Duration {
+        length * self.preset().starting_time / 100
+    }
+
+    pub fn time_reward(&self, reward: Duration) -> Duration {
+        reward * self.preset().time_reward / 100
+    }
+
+    pub fn freeze(&self) -> InputFreeze {
+        InputFreeze::new(self.preset().freeze_frames)
+    }
+
+    pub fn penalty(&self, penalty: Duration) -> Duration {
+        penalty * self.preset().penalty / 100
+    }
+
+    pub fn score(&self, score: usize) -> usize {
+        score * self.preset().score as usize / 100
+    }
+
+    /// Short summary of the preset for menus
+    pub fn describe(&self) -> String {
+        let preset = self.preset();
+        format!(
+            "{:<16} {:>3}% time, {:>3}% penalty, x{:.2} score",
+            self.to_string(),
+            preset.starting_time,
+            preset.penalty,
+            preset.score as f64 / 100.0
+        )
+    }
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Trivial => write!(f, "Trivial"),
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Medium => write!(f, "Medium"),
+            Difficulty::Challenging => write!(f, "Challenging"),
+            Difficulty::Hard => write!(f, "Hard"),
+            Difficulty::Extreme => write!(f, "Extreme"),
+            Difficulty::SuicideMission => write!(f, "Suicide Mission"),
+            Difficulty::Impossible => write!(f, "Impossible"),
+            Difficulty::Helldive => write!(f, "Helldive"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn medium_keeps_the_base_values() {
+        let length = Duration::from_secs(30);
+        let second = Duration::from_secs(1);
+
+        assert_eq!(Difficulty::default(), Difficulty::Medium);
+        assert_eq!(length, Difficulty::Medium.starting_time(length));
+        assert_eq!(second, Difficulty::Medium.penalty(second));
+        assert_eq!(150, Difficulty::Medium.score(150));
+
+        assert_eq!(
+            Duration::from_secs(12),
+            Difficulty::Helldive.starting_time(length)
+        );
+        assert_eq!(450, Difficulty::Helldive.score(150));
+    }
+}
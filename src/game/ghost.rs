@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use super::{replay::Replay, Checkpoint, Game};
+use crate::{error::Result, storage::Records};
+
+/// Progress of a recorded run over time, raced against during a new run
+pub struct Ghost {
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl Ghost {
+    /// Plays `replay` back without a terminal to rebuild its progress
+    pub fn new(replay: Replay) -> Self {
+        let inputs = replay.inputs.clone();
+        let mut player = replay.player();
+        let mut records = Records::default();
+        let mut game = Game::builder(&mut player, &mut records)
+            .playback(replay)
+            .build();
+        game.simulate(&inputs);
+
+        Self {
+            checkpoints: std::mem::take(&mut game.state.checkpoints),
+        }
+    }
+
+    /// Highest scoring replay of every mode that has one
+    pub fn personal_bests() -> Result<Vec<Replay>> {
+        let mut bests: Vec<Replay> = Vec::new();
+
+        for replay in Replay::list()?
+            .iter()
+            .filter_map(|path| Replay::load(path).ok())
+        {
+            match bests.iter_mut().find(|best| best.mode == replay.mode) {
+                Some(best) if best.score < replay.score => *best = replay,
+                Some(_) => (),
+                None => bests.push(replay),
+            }
+        }
+
+        Ok(bests)
+    }
+
+    /// Score and cleared strategems of the ghost at `elapsed` into the run
+    pub fn at(&self, elapsed: Duration) -> (usize, usize) {
+        self.checkpoints
+            .iter()
+            .take_while(|checkpoint| checkpoint.at <= elapsed)
+            .last()
+            .map_or((0, 0), |checkpoint| (checkpoint.score, checkpoint.cleared))
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{Event, KeyEvent, KeyEventKind};
+
+use crate::{
+    error::Result,
+    event::{Controls, Key},
+    strategem::Strategem,
+};
+
+/// Longest a source may wait for input before the game renders a new frame
+pub const FRAME: Duration = Duration::from_millis(17);
+
+/// Where the key presses of a run come from
+pub trait InputSource {
+    /// Waits up to one [`FRAME`] for a key press, returned with the time since `started_at`
+    /// at which it happened
+    fn next(&mut self, started_at: Instant) -> Result<Option<(Duration, Key)>>;
+
+    /// Shows the source the active strategem before every call to [`InputSource::next`],
+    /// only needed by sources that play on their own
+    fn observe(&mut self, _strategem: &Strategem) {}
+}
+
+/// Whether a key was pressed at the keyboard, used by sources that stop on any key
+pub(super) fn key_pressed() -> Result<bool> {
+    Ok(crossterm::event::poll(Duration::ZERO)?
+        && matches!(
+            crossterm::event::read()?,
+            Event::Key(KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            })
+        ))
+}
+
+/// Key presses of a player at the keyboard
+pub struct Keyboard {
+    controls: Controls,
+}
+
+impl Keyboard {
+    pub fn new(controls: Controls) -> Self {
+        Self { controls }
+    }
+}
+
+impl InputSource for Keyboard {
+    fn next(&mut self, started_at: Instant) -> Result<Option<(Duration, Key)>> {
+        if !crossterm::event::poll(FRAME)? {
+            return Ok(None);
+        }
+
+        let key = crate::event::read(&self.controls)?;
+        Ok(key.map(|key| (started_at.elapsed(), key)))
+    }
+}
+
+/// Parses a script of key presses with one `<milliseconds> <up|down|left|right>` pair per line,
+/// the time being measured from the start of the run. Empty lines and `#` comments are skipped
+pub fn parse_script(script: &str) -> Result<Vec<(Duration, Key)>> {
+    let mut inputs: Vec<(Duration, Key)> = Vec::new();
+
+    for (i, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = || format!("Invalid input on line {}: '{line}'", i + 1);
+        let (millis, key) = line.split_once(char::is_whitespace).ok_or_else(invalid)?;
+        let at = Duration::from_millis(millis.parse().map_err(|_| invalid())?);
+        let key = match key.trim().to_lowercase().as_str() {
+            "up" => Key::ArrowUp,
+            "down" => Key::ArrowDown,
+            "left" => Key::ArrowLeft,
+            "right" => Key::ArrowRight,
+            _ => return Err(invalid().into()),
+        };
+
+        if inputs.last().is_some_and(|(last, _)| at < *last) {
+            return Err(format!("Inputs must be in time order, see line {}", i + 1).into());
+        }
+        inputs.push((at, key));
+    }
+
+    Ok(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_are_parsed_in_order() {
+        let script = "# opening\n100 up\n\n250 Down\n250 left\n";
+        let inputs = parse_script(script).unwrap();
+
+        assert_eq!(
+            vec![
+                (Duration::from_millis(100), Key::ArrowUp),
+                (Duration::from_millis(250), Key::ArrowDown),
+                (Duration::from_millis(250), Key::ArrowLeft),
+            ],
+            inputs
+        );
+        assert!(parse_script("300 up\n200 down").is_err());
+        assert!(parse_script("100 jump").is_err());
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    io::ErrorKind,
+    net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use serde::{Deserialize, Serialize};
+
+use super::versus::{Racer, COLUMN, RACE_LENGTH};
+use crate::{
+    error::Result,
+    event::{Controls, Key},
+    rules::Rules,
+    screenln, tui, utility,
+};
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often Escape is checked for while waiting on the other side
+const ESCAPE_POLL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Message {
+    Seed(u64),
+    Completed { cleared: usize, score: usize },
+    Mistake,
+    Finished(Duration),
+    Quit,
+}
+
+/// Message stream to the other instance, incoming messages are read on a separate thread
+struct Connection {
+    stream: TcpStream,
+    inbox: Receiver<Message>,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> Result<Self> {
+        let reader = stream.try_clone()?;
+        let (tx, inbox) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            while let Ok(msg) = bincode::deserialize_from(&reader) {
+                if tx.send(msg).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self { stream, inbox })
+    }
+
+    fn send(&mut self, msg: &Message) -> Result<()> {
+        bincode::serialize_into(&mut self.stream, msg)?;
+        Ok(())
+    }
+
+    /// Returns `Err` once the other side has disconnected
+    fn try_recv(&self) -> core::result::Result<Option<Message>, ()> {
+        match self.inbox.try_recv() {
+            Ok(msg) => Ok(Some(msg)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(()),
+        }
+    }
+
+    #[cfg(test)]
+    fn recv_timeout(&self, timeout: Duration) -> Result<Message> {
+        use std::sync::mpsc::RecvTimeoutError;
+
+        self.inbox.recv_timeout(timeout).map_err(|err| match err {
+            RecvTimeoutError::Timeout => "Timed out waiting for the host".into(),
+            RecvTimeoutError::Disconnected => "Host closed the connection".into(),
+        })
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // Also stops the reader thread, which holds a clone of the stream
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Race against another instance over TCP, both sides play the host's seed
+pub struct LanVersus {
+    conn: Connection,
+    /// Local player first, then the mirrored opponent
+    racers: [Racer; 2],
+    controls: Controls,
+    started_at: Instant,
+    opponent_left: bool,
+    is_running: bool,
+}
+
+impl LanVersus {
+    /// Waits for an opponent on `port`, returns `None` if the player pressed Escape
+    pub fn host(port: u16, seed: u64, controls: Controls, rules: Rules) -> Result<Option<Self>> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+
+        screenln!("Waiting for an opponent on port {port}... (Esc to cancel)")?;
+        let stream = loop {
+            match listener.accept() {
+                Ok((stream, _)) => break stream,
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                    if escape_pressed(ESCAPE_POLL)? {
+                        return Ok(None);
+                    }
+                }
+                Err(err) => return Err(err.into()),
+            }
+        };
+        stream.set_nonblocking(false)?;
+
+        let mut conn = Connection::new(stream)?;
+        conn.send(&Message::Seed(seed))?;
+
+        Ok(Some(Self::new(conn, seed, controls, rules)))
+    }
+
+    /// Connects to a host, `addr` defaults to [`DEFAULT_PORT`] if it has no port.
+    /// Every side scores with its own `rules`, returns `None` if the player pressed Escape
+    pub fn join(addr: &str, controls: Controls, rules: Rules) -> Result<Option<Self>> {
+        screenln!("Connecting to {addr}... (Esc to cancel)")?;
+        let addr = match addr.contains(':') {
+            true => addr.to_string(),
+            false => format!("{addr}:{DEFAULT_PORT}"),
+        };
+
+        // Resolving and connecting block, so they happen apart from the Escape polling
+        let (tx, connected) = mpsc::channel();
+        std::thread::spawn(move || {
+            let host = addr
+                .to_socket_addrs()
+                .ok()
+                .and_then(|mut addrs| addrs.next());
+            let stream = match host {
+                Some(host) => TcpStream::connect_timeout(&host, HANDSHAKE_TIMEOUT)
+                    .map_err(|err| format!("Could not connect to '{addr}': {err}")),
+                None => Err(format!("Could not resolve '{addr}'")),
+            };
+            let _ = tx.send(stream);
+        });
+
+        let stream = loop {
+            match connected.try_recv() {
+                Ok(stream) => break stream?,
+                Err(TryRecvError::Empty) if escape_pressed(ESCAPE_POLL)? => return Ok(None),
+                Err(TryRecvError::Empty) => (),
+                Err(TryRecvError::Disconnected) => return Err("Could not connect".into()),
+            }
+        };
+
+        let conn = Connection::new(stream)?;
+        let waiting_since = Instant::now();
+        loop {
+            match conn.try_recv() {
+                Ok(Some(Message::Seed(seed))) => {
+                    return Ok(Some(Self::new(conn, seed, controls, rules)))
+                }
+                Ok(Some(other)) => {
+                    return Err(format!("Unexpected message from host: {other:?}").into())
+                }
+                Ok(None) if waiting_since.elapsed() >= HANDSHAKE_TIMEOUT => {
+                    return Err("Timed out waiting for the host".into())
+                }
+                Ok(None) if escape_pressed(ESCAPE_POLL)? => return Ok(None),
+                Ok(None) => (),
+                Err(()) => return Err("Host closed the connection".into()),
+            }
+        }
+    }
+
+    fn new(conn: Connection, seed: u64, controls: Controls, rules: Rules) -> Self {
+        Self {
+            conn,
+            racers: [
+                Racer::new("You", seed, rules.clone()),
+                Racer::new("Opponent", seed, rules),
+            ],
+            controls,
+            started_at: Instant::now(),
+            opponent_left: false,
+            is_running: true,
+        }
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        tui::screen::full_clear()?;
+
+        while self.is_running {
+            if crossterm::event::poll(Duration::from_millis(17))? {
+                self.handle_input()?;
+            } else {
+                self.receive();
+                self.print_frame()?;
+                self.update_state();
+
+                if self.is_over() {
+                    return self.handle_race_over();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn send(&mut self, msg: Message) {
+        if self.conn.send(&msg).is_err() {
+            self.opponent_left = true;
+        }
+    }
+
+    fn receive(&mut self) {
+        let opponent = &mut self.racers[1];
+
+        loop {
+            match self.conn.try_recv() {
+                Ok(Some(Message::Completed { cleared, score })) => opponent.sync(cleared, score),
+                Ok(Some(Message::Mistake)) => opponent.record_mistake(),
+                Ok(Some(Message::Finished(time))) => opponent.finish(time),
+                Ok(Some(Message::Seed(_))) => (),
+                Ok(Some(Message::Quit)) | Err(()) => {
+                    self.opponent_left = true;
+                    break;
+                }
+                Ok(None) => break,
+            }
+        }
+    }
+
+    fn handle_input(&mut self) -> Result<()> {
+        match crate::event::read(&self.controls)? {
+            Some(Key::Escape) => {
+                self.send(Message::Quit);
+                tui::screen::clear()?;
+                self.is_running = false;
+            }
+            Some(key) if self.racers[0].press(key.into()) => self.send(Message::Mistake),
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn print_frame(&mut self) -> Result<()> {
+        screenln!(
+            "\x1b[KFirst to clear {RACE_LENGTH} strategems wins! Elapsed: {}",
+            utility::format_duration(self.started_at.elapsed())
+        )?;
+        tui::columns(&self.racers[0].status(), &self.racers[1].status(), COLUMN)?;
+
+        if self.racers[0].finished_in().is_some() {
+            screenln!("\x1b[KWaiting for the opponent to finish...")?;
+        } else {
+            screenln!("\x1b[KControls: {}", self.controls)?;
+        }
+
+        tui::screen::move_back()
+    }
+
+    fn update_state(&mut self) {
+        let you = &mut self.racers[0];
+        if !you.update(self.started_at.elapsed()) {
+            return;
+        }
+
+        let (cleared, score, finished_in) = (you.cleared(), you.score(), you.finished_in());
+        self.send(Message::Completed { cleared, score });
+        if let Some(time) = finished_in {
+            self.send(Message::Finished(time));
+        }
+    }
+
+    /// Both finish times are needed so each side agrees on the winner
+    fn is_over(&self) -> bool {
+        self.opponent_left || self.racers.iter().all(|r| r.finished_in().is_some())
+    }
+
+    fn handle_race_over(&mut self) -> Result<()> {
+        tui::screen::clear()?;
+
+        match (self.racers[0].finished_in(), self.racers[1].finished_in()) {
+            (Some(you), Some(opponent)) if you <= opponent => screenln!("You win the race!")?,
+            (_, Some(_)) => screenln!("The opponent wins the race!")?,
+            _ => screenln!("The opponent left the race")?,
+        }
+        tui::columns(&self.racers[0].status(), &self.racers[1].status(), COLUMN)?;
+
+        tui::confirm_quit(Some("return to game modes"))
+    }
+}
+
+/// Whether Escape was pressed within `timeout`
+fn escape_pressed(timeout: Duration) -> Result<bool> {
+    Ok(crossterm::event::poll(timeout)?
+        && matches!(
+            crossterm::event::read()?,
+            Event::Key(KeyEvent {
+                code: KeyCode::Esc,
+                kind: KeyEventKind::Press,
+                ..
+            })
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_cross_localhost() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut client = Connection::new(TcpStream::connect(addr).unwrap()).unwrap();
+        let mut host = Connection::new(listener.accept().unwrap().0).unwrap();
+
+        host.send(&Message::Seed(42)).unwrap();
+        client
+            .send(&Message::Completed {
+                cleared: 3,
+                score: 250,
+            })
+            .unwrap();
+
+        let timeout = Duration::from_secs(1);
+        assert_eq!(Message::Seed(42), client.recv_timeout(timeout).unwrap());
+        assert_eq!(
+            Message::Completed {
+                cleared: 3,
+                score: 250
+            },
+            host.recv_timeout(timeout).unwrap()
+        );
+
+        drop(client);
+        host.recv_timeout(timeout).unwrap_err();
+    }
+}
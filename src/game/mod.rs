@@ -0,0 +1,1397 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crossterm::style::Stylize;
+use serde::{Deserialize, Serialize};
+
+use rand::Rng;
+
+use crate::{
+    error::Result,
+    event::{Controls, Key},
+    rules::Rules,
+    screenln,
+    storage::{Leaderboard, PlayerData, Records, Storage},
+    strategem::{Strategem, StrategemKey, StrategemSource},
+    tui,
+    utility::{self, FreezeState, GameTimer, InputFreeze},
+};
+
+pub use difficulty::Difficulty;
+use ghost::Ghost;
+use input::{InputSource, Keyboard};
+pub use modifier::{Modifier, Modifiers};
+use replay::{Playback, Replay};
+use round::{Round, RoundBonus, INTERMISSION};
+
+pub mod bot;
+mod difficulty;
+pub mod ghost;
+pub mod input;
+pub mod lan;
+mod modifier;
+pub mod replay;
+mod round;
+pub mod versus;
+
+pub const SPRINT_LENGTHS: [usize; 2] = [20, 50];
+
+/// Time a run starts with unless the player picks another length
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(30);
+
+/// How many upcoming strategems are shown below the active one
+const PREVIEW_LEN: usize = 4;
+
+/// Interval between updates of the game state, on the run's clock
+const TICK: Duration = Duration::from_millis(17);
+
+/// How long the speed bonus of a cleared strategem is shown next to the score
+const SPEED_POPUP: Duration = Duration::from_millis(1200);
+
+/// How long a simulated run goes on after its last input
+const SIMULATION_LIMIT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Rounds with a fixed queue of strategems and end of round bonuses
+    #[default]
+    Classic,
+    Endless,
+    Sprint(usize),
+    /// One continuous timer without rounds, with a strategem order shared by everyone
+    /// on the given day
+    Daily(u64),
+    /// One continuous timer without rounds, drawing only from the player's loadout, not ranked
+    Loadout,
+    /// One continuous timer without rounds, where the first wrong input ends the run
+    Hardcore,
+    /// Codes are hidden after being shown for the given time
+    Memory(Duration),
+}
+
+impl GameMode {
+    /// Time added to the timer after clearing `cleared` strategems
+    pub fn time_reward(&self, cleared: usize, rules: &Rules) -> Duration {
+        match self {
+            GameMode::Endless => {
+                (rules.time_reward() * 20 / (20 + cleared as u32)).max(rules.min_time_reward())
+            }
+            _ => rules.time_reward(),
+        }
+    }
+
+    pub fn next_strategem(&self, source: &mut StrategemSource, score: usize) -> Strategem {
+        match self {
+            GameMode::Endless => source.next_biased(score as f64 / 20000.0),
+            _ => source.next(),
+        }
+    }
+
+    pub fn is_round_based(&self) -> bool {
+        matches!(self, GameMode::Classic)
+    }
+
+    /// Seed that fixes the strategem order of the run, if the mode requires one
+    pub fn seed(&self) -> Option<u64> {
+        match self {
+            GameMode::Daily(day) => Some(day.wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+            _ => None,
+        }
+    }
+
+    /// Name of the separate leaderboard for the mode, `None` for the classic one
+    pub fn leaderboard(&self) -> Option<String> {
+        match self {
+            GameMode::Endless => Some("Endless".to_string()),
+            GameMode::Hardcore => Some("Hardcore".to_string()),
+            GameMode::Memory(preview) => {
+                Some(format!("Memory {}", utility::format_duration(*preview)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Picks the seed of a run, the mode's own seed wins over the requested one
+    fn pick_seed(&self, seed: Option<u64>) -> u64 {
+        self.seed()
+            .or(seed)
+            .unwrap_or_else(|| rand::thread_rng().gen())
+    }
+}
+
+impl std::fmt::Display for GameMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameMode::Classic => write!(f, "Classic"),
+            GameMode::Endless => write!(f, "Endless"),
+            GameMode::Sprint(target) => write!(f, "Sprint {target}"),
+            GameMode::Daily(day) => write!(f, "Daily {}", utility::format_day(*day)),
+            GameMode::Loadout => write!(f, "Loadout"),
+            GameMode::Hardcore => write!(f, "Hardcore"),
+            GameMode::Memory(preview) => {
+                write!(f, "Memory {}", utility::format_duration(*preview))
+            }
+        }
+    }
+}
+
+/// Outcome of a finished run
+pub struct RunSummary {
+    pub score: usize,
+    pub best_streak: usize,
+    pub accuracy: f64,
+}
+
+/// How a single strategem of a run was cleared
+pub struct StrategemResult {
+    pub name: &'static str,
+    /// Time from the strategem's first appearance to its completion
+    pub time: Duration,
+    pub mistakes: usize,
+}
+
+/// Score and cleared strategems right after a strategem was cleared
+struct Checkpoint {
+    at: Duration,
+    score: usize,
+    cleared: usize,
+}
+
+struct GameState {
+    game_timer: GameTimer,
+    score: usize,
+    streak: usize,
+    best_streak: usize,
+    cleared: usize,
+    keystrokes: usize,
+    mistakes: usize,
+    /// Time since the run started, everything in the run is measured on this clock
+    now: Duration,
+    next_tick: Duration,
+    /// When the active strategem was first shown
+    appeared_at: Duration,
+    /// When the active strategem was shown or reset after a mistake
+    shown_at: Duration,
+    /// Mistakes made on the active strategem
+    strategem_mistakes: usize,
+    /// Last key press or newly shown strategem, the multiplier decays when idling since then
+    idle_since: Duration,
+    modifiers: Modifiers,
+    /// Keys of the active strategem hidden by the Ion Storm modifier
+    obscured: u16,
+    /// Speed bonus of the last cleared strategem and when it was awarded
+    speed_popup: Option<(usize, Duration)>,
+    source: StrategemSource,
+    /// Names of every strategem drawn in the run, in order
+    drawn: Vec<&'static str>,
+    checkpoints: Vec<Checkpoint>,
+    results: Vec<StrategemResult>,
+    /// Active strategem followed by the upcoming ones, never empty once started
+    queue: VecDeque<Strategem>,
+    round: Option<Round>,
+    /// Summary of the last round and when it was cleared, shown between rounds
+    intermission: Option<(RoundBonus, Duration)>,
+}
+
+impl GameState {
+    fn new(
+        game_timer: GameTimer,
+        mode: GameMode,
+        source: StrategemSource,
+        modifiers: Modifiers,
+    ) -> Self {
+        let mut state = Self {
+            game_timer,
+            score: 0,
+            streak: 0,
+            best_streak: 0,
+            cleared: 0,
+            keystrokes: 0,
+            mistakes: 0,
+            now: Duration::ZERO,
+            next_tick: Duration::ZERO,
+            appeared_at: Duration::ZERO,
+            shown_at: Duration::ZERO,
+            strategem_mistakes: 0,
+            idle_since: Duration::ZERO,
+            modifiers,
+            obscured: 0,
+            speed_popup: None,
+            source,
+            drawn: Vec::new(),
+            checkpoints: Vec::new(),
+            results: Vec::new(),
+            queue: VecDeque::new(),
+            round: None,
+            intermission: None,
+        };
+
+        state.start(mode);
+        state
+    }
+
+    fn reset(&mut self, mode: GameMode, seed: Option<u64>) {
+        self.set_now(Duration::ZERO);
+        self.next_tick = Duration::ZERO;
+        self.score = 0;
+        self.streak = 0;
+        self.best_streak = 0;
+        self.cleared = 0;
+        self.keystrokes = 0;
+        self.mistakes = 0;
+        self.source.reseed(mode.pick_seed(seed));
+        self.start(mode);
+    }
+
+    /// Starts the first round, or fills the queue for modes without rounds
+    fn start(&mut self, mode: GameMode) {
+        self.queue.clear();
+        self.drawn.clear();
+        self.checkpoints.clear();
+        self.results.clear();
+        self.round = None;
+        self.intermission = None;
+        self.speed_popup = None;
+        self.modifiers.roll(self.source.seed());
+        self.reset_timer();
+
+        if mode.is_round_based() {
+            self.start_round(1);
+        } else {
+            self.refill(mode);
+            self.mark_shown();
+        }
+    }
+
+    /// Restarts the timer from its starting time, minus what the modifiers take away
+    fn reset_timer(&mut self) {
+        self.game_timer.reset();
+        let cut = self.modifiers.time_cut(self.game_timer.initial_duration());
+        self.game_timer.sub(cut);
+    }
+
+    fn start_round(&mut self, number: usize) {
+        let round = Round::new(number);
+        self.queue = (0..round.queue_len()).map(|_| self.source.next()).collect();
+        self.drawn.extend(self.queue.iter().map(Strategem::name));
+        self.round = Some(round);
+        self.intermission = None;
+        self.mark_shown();
+    }
+
+    fn refill(&mut self, mode: GameMode) {
+        while self.queue.len() <= PREVIEW_LEN {
+            let strategem = mode.next_strategem(&mut self.source, self.score);
+            self.drawn.push(strategem.name());
+            self.queue.push_back(strategem);
+        }
+    }
+
+    /// Starts timing a newly shown active strategem and applies the modifiers to it
+    fn mark_shown(&mut self) {
+        self.appeared_at = self.now;
+        self.shown_at = self.now;
+        self.idle_since = self.now;
+        self.strategem_mistakes = 0;
+
+        // Keys picked by the modifiers only depend on the seed and the strategem's position
+        let seed =
+            self.source.seed() ^ (self.cleared as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        self.obscured = match self.queue.front_mut() {
+            Some(strategem) => self.modifiers.prepare(strategem, seed),
+            None => 0,
+        };
+    }
+
+    fn set_now(&mut self, now: Duration) {
+        self.now = now;
+        self.game_timer.set_now(now);
+    }
+
+    fn strategem(&self) -> &Strategem {
+        &self.queue[0]
+    }
+
+    fn summary(&self) -> RunSummary {
+        let hits = self.keystrokes.saturating_sub(self.mistakes);
+
+        RunSummary {
+            score: self.score,
+            best_streak: self.best_streak,
+            accuracy: hits as f64 / self.keystrokes.max(1) as f64 * 100.0,
+        }
+    }
+
+    fn strategem_mut(&mut self) -> &mut Strategem {
+        &mut self.queue[0]
+    }
+
+    fn upcoming(&self) -> impl Iterator<Item = &Strategem> {
+        self.queue.iter().skip(1).take(PREVIEW_LEN)
+    }
+
+    /// Moves on to the next strategem, clearing the round once its queue runs out.
+    /// Round bonuses are scaled by the `difficulty` and modifiers like strategem scores
+    fn advance(&mut self, mode: GameMode, difficulty: Difficulty) {
+        match &self.round {
+            Some(round) if self.queue.len() == 1 => {
+                let bonus = round
+                    .bonus(self.game_timer.remaining())
+                    .scaled(|points| self.modifiers.score(difficulty.score(points)));
+                self.score += bonus.total();
+                self.intermission = Some((bonus, self.now));
+            }
+            Some(_) => {
+                self.queue.pop_front();
+                self.mark_shown();
+            }
+            None => {
+                self.queue.pop_front();
+                self.refill(mode);
+                self.mark_shown();
+            }
+        }
+    }
+}
+
+pub struct Game<'a> {
+    state: GameState,
+    player: &'a mut PlayerData,
+    /// Upgrades the run is played with, none for daily runs
+    upgrades: PlayerData,
+    records: &'a mut Records,
+    freeze: InputFreeze,
+    controls: Controls,
+    input: Box<dyn InputSource>,
+    mode: GameMode,
+    difficulty: Difficulty,
+    rules: Rules,
+    /// Chosen game length, the run starts with it scaled by the difficulty
+    length: Duration,
+    seed: Option<u64>,
+    pool: Vec<&'static str>,
+    name: String,
+    /// Ends the game after one run instead of offering a restart
+    single_run: bool,
+    /// Key presses of the run with the time they happened at
+    inputs: Vec<(Duration, Key)>,
+    /// Replay being played back, such runs are neither recorded nor ranked
+    playback: Option<Replay>,
+    ghost: Option<Ghost>,
+    /// Attract mode run, nothing about it is saved
+    demo: bool,
+    /// Whether the daily run being played is the day's ranked attempt
+    daily_ranked: bool,
+    is_running: bool,
+}
+
+impl<'a> Game<'a> {
+    pub fn builder(player: &'a mut PlayerData, records: &'a mut Records) -> GameBuilder<'a> {
+        GameBuilder::new(player, records)
+    }
+
+    pub fn summary(&self) -> RunSummary {
+        self.state.summary()
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.state.source.seed()
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn modifiers(&self) -> &Modifiers {
+        &self.state.modifiers
+    }
+
+    pub fn cleared(&self) -> usize {
+        self.state.cleared
+    }
+
+    /// Strategems cleared so far, in order
+    pub fn results(&self) -> &[StrategemResult] {
+        &self.state.results
+    }
+
+    pub fn run(&mut self) -> Result<bool> {
+        let mut restart = false;
+        let mut started_at = Instant::now();
+
+        tui::screen::full_clear()?;
+        self.start_daily_attempt()?;
+
+        while self.is_running {
+            self.input.observe(self.state.strategem());
+            match self.input.next(started_at)? {
+                Some((at, key)) => self.handle_input(at, key)?,
+                None => {
+                    self.catch_up(started_at.elapsed());
+                    self.print_frame()?;
+                }
+            }
+
+            if self.is_running && self.is_over() {
+                restart = self.handle_game_over()?;
+                started_at = Instant::now();
+            }
+        }
+
+        Ok(restart)
+    }
+
+    /// Plays the run without a terminal, feeding `inputs` at the time they happened at
+    pub fn simulate(&mut self, inputs: &[(Duration, Key)]) {
+        for &(at, key) in inputs {
+            self.catch_up(at);
+            if key != Key::Escape && !self.is_over() {
+                self.press(key.into());
+            }
+        }
+
+        let last = inputs.last().map_or(Duration::ZERO, |(at, _)| *at);
+        self.catch_up(last + SIMULATION_LIMIT);
+    }
+
+    /// Updates the state on every tick up to `now`, stopping early if the run is over
+    fn catch_up(&mut self, now: Duration) {
+        while self.state.next_tick <= now && !self.is_over() {
+            self.state.set_now(self.state.next_tick);
+            self.update_state();
+            self.state.next_tick += TICK;
+        }
+
+        if !self.is_over() {
+            self.state.set_now(now.max(self.state.now));
+        }
+    }
+
+    fn handle_input(&mut self, at: Duration, key: Key) -> Result<()> {
+        self.catch_up(at);
+
+        match key {
+            Key::Escape => {
+                tui::screen::clear()?;
+                self.is_running = false;
+                if let GameMode::Daily(day) = self.mode {
+                    self.save_daily_result(day)?;
+                }
+            }
+            _ if self.is_over() => (),
+            key => {
+                self.inputs.push((at, key));
+                self.press(key.into());
+            }
+        };
+
+        Ok(())
+    }
+
+    fn press(&mut self, key: StrategemKey) {
+        let strategem = self.state.strategem_mut();
+        let was_valid = strategem.is_valid() && !strategem.is_completed();
+        strategem.assert_key(key);
+        self.state.idle_since = self.state.now;
+
+        if was_valid && self.state.intermission.is_none() {
+            self.state.keystrokes += 1;
+        }
+
+        if was_valid && !self.state.strategem().is_valid() {
+            self.state.mistakes += 1;
+            self.state.strategem_mistakes += 1;
+            if let Some(round) = self.state.round.as_mut() {
+                round.add_mistake();
+            }
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        match self.mode {
+            GameMode::Sprint(target) => self.state.cleared >= target,
+            GameMode::Hardcore => {
+                self.state.game_timer.is_over() || !self.state.strategem().is_valid()
+            }
+            _ => self.state.intermission.is_none() && self.state.game_timer.is_over(),
+        }
+    }
+
+    fn print_frame(&mut self) -> Result<()> {
+        if let GameMode::Sprint(target) = self.mode {
+            screenln!("Cleared: {}/{}", self.state.cleared, target)?;
+            screenln!("Time: {}", utility::format_duration(self.state.now))?;
+        } else {
+            let popup = match self.state.speed_popup {
+                Some((bonus, at)) if self.state.now - at < SPEED_POPUP => {
+                    format!("+{bonus} speed").green().to_string()
+                }
+                _ => String::new(),
+            };
+            screenln!(
+                "Score: {} {} {}\x1b[K",
+                self.state.score,
+                self.rules.multiplier(self.state.streak),
+                popup
+            )?;
+            screenln!("{}", self.state.game_timer)?;
+        }
+
+        if let Some(ghost) = &self.ghost {
+            let (score, cleared) = ghost.at(self.state.now);
+            let lead = match self.state.score.cmp(&score) {
+                std::cmp::Ordering::Greater => format!("{} ahead", self.state.score - score),
+                std::cmp::Ordering::Less => format!("{} behind", score - self.state.score),
+                std::cmp::Ordering::Equal => String::from("even"),
+            };
+            screenln!("\x1b[KGhost: {score} ({lead}), {cleared} cleared")?;
+        }
+
+        if !self.state.modifiers.active().is_empty() {
+            screenln!("\x1b[KModifiers: {}", self.state.modifiers)?;
+        }
+
+        self.print_queue()?;
+        if self.demo {
+            screenln!("\x1b[KDemo, press any key to play")?;
+        } else {
+            screenln!("Controls: {}", self.controls)?;
+        }
+
+        tui::screen::move_back()
+    }
+
+    /// Prints the active strategem with the upcoming ones, or the summary between rounds
+    fn print_queue(&self) -> Result<()> {
+        let mut lines = Vec::new();
+
+        if let Some(round) = &self.state.round {
+            match &self.state.intermission {
+                Some(_) => lines.push(format!("Round {} complete!", round.number())),
+                None => lines.push(format!("Round {}", round.number())),
+            }
+        }
+
+        match &self.state.intermission {
+            Some((bonus, _)) => {
+                lines.push(format!("Round bonus:   {}", bonus.round));
+                lines.push(format!("Time bonus:    {}", bonus.time));
+                lines.push(format!("Perfect bonus: {}", bonus.perfect));
+            }
+            None => {
+                let strategem = match self.mode {
+                    GameMode::Memory(preview)
+                        if self.state.now - self.state.shown_at >= preview =>
+                    {
+                        self.state.strategem().masked().to_string()
+                    }
+                    _ if self.state.obscured != 0 => self
+                        .state
+                        .strategem()
+                        .obscured(self.state.obscured)
+                        .to_string(),
+                    _ => self.state.strategem().to_string(),
+                };
+                lines.extend(strategem.lines().map(String::from));
+                lines.push(String::from("Up next:"));
+                lines.extend(self.state.upcoming().map(utility::format_strategem_name));
+            }
+        }
+
+        let frame_len = usize::from(self.state.round.is_some()) + 3 + PREVIEW_LEN;
+        lines.resize(frame_len, String::new());
+        lines.iter().try_for_each(|line| screenln!("\x1b[K{line}"))
+    }
+
+    fn update_state(&mut self) {
+        if let Some((_, cleared_at)) = &self.state.intermission {
+            if self.state.now - *cleared_at >= INTERMISSION {
+                self.start_next_round();
+            }
+            return;
+        }
+
+        let streak = self
+            .rules
+            .decay(self.state.streak, self.state.now - self.state.idle_since);
+        if streak != self.state.streak {
+            self.state.streak = streak;
+            self.state.idle_since = self.state.now;
+        }
+
+        if self.state.strategem().is_completed() {
+            let base_reward = self.mode.time_reward(self.state.cleared, &self.rules);
+            self.state.streak += 1;
+            self.state.best_streak = self.state.best_streak.max(self.state.streak);
+            self.state.cleared += 1;
+            let score = self.difficulty.score(self.rules.score(
+                self.state.strategem().difficulty(),
+                self.rules.multiplier(self.state.streak),
+                self.upgrades.bonus_score(),
+            ));
+            self.state.score += self.state.modifiers.score(score);
+            let time = self.state.now - self.state.appeared_at;
+            let code_len = self.state.strategem().code_len();
+            let speed_bonus = self
+                .difficulty
+                .score(self.rules.speed_bonus(time, code_len));
+            let speed_bonus = self.state.modifiers.score(speed_bonus);
+            if speed_bonus > 0 {
+                self.state.score += speed_bonus;
+                self.state.speed_popup = Some((speed_bonus, self.state.now));
+            }
+            let reward = self.difficulty.time_reward(base_reward);
+            self.state
+                .game_timer
+                .add(self.state.modifiers.time_reward(reward) + self.upgrades.time_reward_dur());
+            self.state.results.push(StrategemResult {
+                name: self.state.strategem().name(),
+                time,
+                mistakes: self.state.strategem_mistakes,
+            });
+            self.state.advance(self.mode, self.difficulty);
+            self.state.checkpoints.push(Checkpoint {
+                at: self.state.now,
+                score: self.state.score,
+                cleared: self.state.cleared,
+            });
+        } else if !self.state.strategem().is_valid() {
+            self.state.streak = 0;
+            if self.mode == GameMode::Hardcore {
+                return;
+            }
+
+            if let FreezeState::Completed = self.freeze.ping() {
+                self.state.strategem_mut().reset();
+                self.state.shown_at = self.state.now;
+                if !matches!(self.mode, GameMode::Sprint(_)) {
+                    let penalty = self.rules.penalty(self.upgrades.penalty_debuff_dur());
+                    let penalty = self.difficulty.penalty(penalty);
+                    let penalty = self.state.modifiers.penalty(penalty);
+                    self.state.game_timer.sub(penalty);
+                }
+            };
+        }
+    }
+
+    fn start_next_round(&mut self) {
+        let number = self.state.round.as_ref().map_or(0, Round::number) + 1;
+        self.state.start_round(number);
+        self.state.reset_timer();
+    }
+
+    fn handle_game_over(&mut self) -> Result<bool> {
+        let mut _sc = tui::screen::cleaner();
+
+        tui::screen::clear()?;
+        if self.demo {
+            self.is_running = false;
+            return Ok(false);
+        }
+
+        if let Some(replay) = &self.playback {
+            screenln!(
+                "Replay finished with {} Democracy Points (recorded: {})",
+                self.state.score,
+                replay.score
+            )?;
+            tui::confirm_quit(Some("return to replays"))?;
+            self.is_running = false;
+            return Ok(false);
+        }
+
+        self.replay().save()?;
+        match self.mode {
+            GameMode::Sprint(target) => self.handle_sprint_over(target)?,
+            GameMode::Daily(day) => self.handle_daily_over(day)?,
+            GameMode::Loadout => screenln!(
+                "Game Over! You scored {} Democracy Points, loadout runs are not ranked",
+                self.state.score
+            )?,
+            _ => self.handle_score_over()?,
+        }
+
+        self.player.add_to_wallet(self.state.score);
+        self.player.save()?;
+
+        screenln!("Seed: {}", self.state.source.seed())?;
+        if self.single_run {
+            tui::confirm_quit(Some("continue"))?;
+            self.is_running = false;
+            return Ok(false);
+        }
+
+        screenln!("Restart the game [y/n]?")?;
+        if tui::confirm_action()? {
+            self.state.reset(self.mode, self.seed);
+            self.freeze.reset();
+            self.inputs.clear();
+            self.start_daily_attempt()?;
+        } else {
+            self.is_running = false;
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// Recording of the run, enough to play it back with the same results
+    fn replay(&self) -> Replay {
+        Replay {
+            mode: self.mode,
+            seed: self.state.source.seed(),
+            recorded_at: utility::unix_millis(),
+            difficulty: self.difficulty,
+            modifiers: self.state.modifiers.active().to_vec(),
+            rules: self.rules.clone(),
+            duration: self.length,
+            penalty: self.upgrades.penalty_debuff_dur(),
+            time_reward: self.upgrades.time_reward_dur(),
+            bonus_score: self.upgrades.bonus_score(),
+            pool: self.pool.iter().map(|name| name.to_string()).collect(),
+            sequence: self
+                .state
+                .drawn
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            inputs: self.inputs.clone(),
+            score: self.state.score,
+            cleared: self.state.cleared,
+        }
+    }
+
+    fn handle_score_over(&mut self) -> Result<()> {
+        let score = self.state.score;
+        if self.mode == GameMode::Hardcore && !self.state.strategem().is_valid() {
+            screenln!(
+                "Game Over! A wrong input ended your run, you scored {score} Democracy Points"
+            )?;
+        } else {
+            screenln!("Game Over! You scored {score} Democracy Points")?;
+        }
+
+        let board = self.leaderboard();
+        let leaderboard = self.records.leaderboard_mut(board.as_deref());
+        Self::print_leaderboard(board.as_deref(), leaderboard, &self.name, score)?;
+
+        if leaderboard.get(&self.name).is_none_or(|best| score > best) {
+            leaderboard.insert(&self.name, score);
+        }
+
+        self.records.save_leaderboards()
+    }
+
+    /// Name of the leaderboard the run is ranked on, runs of another length than
+    /// [`DEFAULT_DURATION`] or another difficulty than the default are ranked apart
+    fn leaderboard(&self) -> Option<String> {
+        let mut variant = Vec::new();
+        if self.length != DEFAULT_DURATION {
+            variant.push(format!("{}s", self.length.as_secs()));
+        }
+        if self.difficulty != Difficulty::default() {
+            variant.push(self.difficulty.to_string());
+        }
+
+        let board = self.mode.leaderboard();
+        if variant.is_empty() {
+            return board;
+        }
+
+        Some(format!(
+            "{} {}",
+            board.as_deref().unwrap_or("Classic"),
+            variant.join(" ")
+        ))
+    }
+
+    fn handle_sprint_over(&mut self, target: usize) -> Result<()> {
+        let elapsed = self.state.now;
+        let millis = elapsed.as_millis() as u64;
+        let is_record = self
+            .records
+            .sprint
+            .best(target, &self.name)
+            .is_none_or(|best| millis < best);
+
+        screenln!(
+            "Sprint complete! You cleared {} strategems in {}",
+            target,
+            utility::format_duration(elapsed)
+        )?;
+
+        if is_record {
+            self.records.sprint.insert(target, &self.name, millis);
+        }
+
+        screenln!("Fastest times:")?;
+        self.records
+            .sprint
+            .sorted_vec(target)
+            .iter()
+            .enumerate()
+            .for_each(|(i, rec)| {
+                let time = utility::format_duration(Duration::from_millis(*rec.1));
+                if is_record && rec.0.eq(&self.name) {
+                    screenln!("  {}. {:<18} {} New record!", i + 1, rec.0, time).unwrap();
+                } else {
+                    screenln!("  {}. {:<18} {}", i + 1, rec.0, time).unwrap();
+                }
+            });
+
+        self.records.sprint.save()
+    }
+
+    /// Claims the day's ranked attempt when a daily run starts, so quitting part-way
+    /// through does not give another try at an already seen sequence
+    fn start_daily_attempt(&mut self) -> Result<()> {
+        self.daily_ranked = false;
+        let GameMode::Daily(day) = self.mode else {
+            return Ok(());
+        };
+        if self.playback.is_some() || self.demo || self.records.daily.get(day).is_some() {
+            return Ok(());
+        }
+
+        self.daily_ranked = true;
+        self.records.daily.insert(day, 0);
+        self.records.daily.save()
+    }
+
+    /// Saves the score of the day's ranked attempt, if this run is the one
+    fn save_daily_result(&mut self, day: u64) -> Result<()> {
+        if !self.daily_ranked {
+            return Ok(());
+        }
+
+        self.daily_ranked = false;
+        self.records.daily.insert(day, self.state.score);
+        self.records.daily.save()
+    }
+
+    fn handle_daily_over(&mut self, day: u64) -> Result<()> {
+        screenln!(
+            "Daily Challenge {} is over! You scored {} Democracy Points",
+            utility::format_day(day),
+            self.state.score
+        )?;
+
+        if self.daily_ranked {
+            self.save_daily_result(day)?;
+            screenln!("Ranked result for today is saved")?;
+        } else if let Some(score) = self.records.daily.get(day) {
+            screenln!("Your ranked result for today is {score}, this attempt is unranked")?;
+        }
+
+        screenln!("Recent daily results:")?;
+        self.records
+            .daily
+            .recent(5)
+            .iter()
+            .for_each(|(day, score)| {
+                screenln!("  {}  {}", utility::format_day(*day), score).unwrap()
+            });
+
+        self.records.daily.save()
+    }
+
+    fn print_leaderboard(
+        board: Option<&str>,
+        leaderboard: &Leaderboard,
+        name: &str,
+        curr_score: usize,
+    ) -> Result<()> {
+        match board {
+            Some(board) => screenln!("{board} leaderboard:")?,
+            None => screenln!("Leaderboard:")?,
+        }
+
+        if leaderboard.get(name).is_none() {
+            screenln!("  -. {:<18} {} New record!", name, curr_score)?;
+        }
+
+        leaderboard
+            .sorted_vec()
+            .iter()
+            .enumerate()
+            .for_each(|(i, rec)| {
+                if rec.0.eq(name) && &curr_score > rec.1 {
+                    screenln!("  {}. {:<18} {} New record!", i + 1, rec.0, curr_score).unwrap();
+                } else {
+                    screenln!("  {}. {:<18} {}", i + 1, rec.0, rec.1).unwrap();
+                }
+            });
+
+        Ok(())
+    }
+}
+
+pub struct GameBuilder<'a> {
+    player: &'a mut PlayerData,
+    records: &'a mut Records,
+    game_timer: GameTimer,
+    controls: Controls,
+    input: Option<Box<dyn InputSource>>,
+    mode: GameMode,
+    difficulty: Difficulty,
+    modifiers: Modifiers,
+    rules: Rules,
+    seed: Option<u64>,
+    pool: Vec<Strategem>,
+    name: String,
+    single_run: bool,
+    playback: Option<Replay>,
+    ghost: Option<Ghost>,
+    demo: bool,
+}
+
+impl<'a> GameBuilder<'a> {
+    pub fn new(player: &'a mut PlayerData, records: &'a mut Records) -> Self {
+        Self {
+            player,
+            records,
+            game_timer: GameTimer::start_from(DEFAULT_DURATION),
+            controls: Controls::arrows(),
+            input: None,
+            mode: GameMode::default(),
+            difficulty: Difficulty::default(),
+            modifiers: Modifiers::default(),
+            rules: Rules::default(),
+            seed: None,
+            pool: Vec::new(),
+            name: String::from("You"),
+            single_run: false,
+            playback: None,
+            ghost: None,
+            demo: false,
+        }
+    }
+
+    pub fn timer(mut self, game_timer: GameTimer) -> Self {
+        self.game_timer = game_timer;
+        self
+    }
+
+    pub fn controls(mut self, controls: Controls) -> Self {
+        self.controls = controls;
+        self
+    }
+
+    /// Where the key presses come from, the keyboard with the game's controls by default
+    pub fn input(mut self, input: impl InputSource + 'static) -> Self {
+        self.input = Some(Box::new(input));
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    pub fn modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub fn rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn mode(mut self, mode: GameMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Restricts the run to `pool`, an empty pool draws from every strategem
+    pub fn pool(mut self, pool: Vec<Strategem>) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Name the run's results are saved under
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn single_run(mut self) -> Self {
+        self.single_run = true;
+        self
+    }
+
+    /// Attract mode run that ends without a game over screen and is not saved
+    pub fn demo(mut self) -> Self {
+        self.demo = true;
+        self
+    }
+
+    /// Plays `replay` back with the settings it was recorded with
+    pub fn playback(mut self, replay: Replay) -> Self {
+        self = self.replay_settings(&replay);
+        self.input = Some(Box::new(Playback::new(&replay)));
+        self.playback = Some(replay);
+        self
+    }
+
+    /// Races the run against `ghost`, with the settings of the replay it was built from
+    pub fn ghost(mut self, replay: Replay) -> Self {
+        self = self.replay_settings(&replay);
+        self.ghost = Some(Ghost::new(replay));
+        self
+    }
+
+    /// Takes every setting that affects the run from `replay`
+    fn replay_settings(mut self, replay: &Replay) -> Self {
+        self.game_timer = GameTimer::start_from(replay.duration);
+        self.difficulty = replay.difficulty;
+        self.modifiers = Modifiers::new(replay.modifiers.clone());
+        self.rules = replay.rules.clone();
+        self.mode = replay.mode;
+        self.seed = Some(replay.seed);
+        self.pool = replay.pool();
+        self
+    }
+
+    pub fn build(self) -> Game<'a> {
+        let pool = self.pool.iter().map(Strategem::name).collect();
+        let source = StrategemSource::new(self.mode.pick_seed(self.seed)).with_pool(self.pool);
+        let input = self
+            .input
+            .unwrap_or_else(|| Box::new(Keyboard::new(self.controls.clone())));
+        // Daily runs are ranked together, so they all get the same length
+        let length = match self.mode {
+            GameMode::Daily(_) => DEFAULT_DURATION,
+            _ => self.game_timer.initial_duration(),
+        };
+        // Sprint times and daily runs are ranked regardless of the chosen preset
+        let difficulty = match self.mode {
+            GameMode::Sprint(_) | GameMode::Daily(_) => Difficulty::default(),
+            _ => self.difficulty,
+        };
+        // Everyone plays the daily run with the modifiers rolled from its seed,
+        // the default rules and no upgrades
+        let (modifiers, rules, upgrades) = match self.mode {
+            GameMode::Daily(_) => (Modifiers::random(), Rules::default(), PlayerData::default()),
+            // Sprint times share one board, so they are all set without modifiers
+            GameMode::Sprint(_) => (Modifiers::default(), self.rules, self.player.clone()),
+            _ => (self.modifiers, self.rules, self.player.clone()),
+        };
+        let game_timer = GameTimer::start_from(difficulty.starting_time(length));
+
+        Game {
+            state: GameState::new(game_timer, self.mode, source, modifiers),
+            player: self.player,
+            upgrades,
+            records: self.records,
+            freeze: difficulty.freeze(),
+            controls: self.controls,
+            input,
+            mode: self.mode,
+            difficulty,
+            rules,
+            length,
+            seed: self.seed,
+            pool,
+            name: self.name,
+            single_run: self.single_run,
+            inputs: Vec::new(),
+            playback: self.playback,
+            ghost: self.ghost,
+            demo: self.demo,
+            daily_ranked: false,
+            is_running: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endless_reward_decays() {
+        let rules = Rules::default();
+        let first = GameMode::Endless.time_reward(0, &rules);
+        let later = GameMode::Endless.time_reward(40, &rules);
+
+        assert_eq!(GameMode::Classic.time_reward(40, &rules), first);
+        assert!(later < first);
+        assert_eq!(
+            rules.min_time_reward(),
+            GameMode::Endless.time_reward(10_000, &rules)
+        );
+    }
+
+    fn take_names(state: &mut GameState, mode: GameMode, count: usize) -> Vec<&'static str> {
+        (0..count)
+            .map(|_| {
+                let name = state.strategem().name();
+                state.advance(mode, Difficulty::default());
+                name
+            })
+            .collect()
+    }
+
+    #[test]
+    fn daily_order_is_shared() {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let mut first = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Daily(20_000))
+            .seed(Some(1))
+            .build()
+            .state;
+
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let mut second = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Daily(20_000))
+            .seed(Some(2))
+            .build()
+            .state;
+
+        assert_eq!(first.source.seed(), second.source.seed());
+        assert_eq!(
+            take_names(&mut first, GameMode::Daily(20_000), 10),
+            take_names(&mut second, GameMode::Daily(20_000), 10)
+        );
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let mut game = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Endless)
+            .seed(Some(42))
+            .build();
+
+        let names = take_names(&mut game.state, game.mode, 20);
+        game.state.reset(game.mode, game.seed);
+
+        assert_eq!(42, game.state.source.seed());
+        assert_eq!(names, take_names(&mut game.state, game.mode, 20));
+    }
+
+    #[test]
+    fn queue_keeps_preview_filled() {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let mut game = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Endless)
+            .build();
+
+        for _ in 0..10 {
+            assert_eq!(PREVIEW_LEN, game.state.upcoming().count());
+            game.state.advance(game.mode, game.difficulty);
+        }
+    }
+
+    #[test]
+    fn loadout_pool_restricts_strategems() {
+        let pool = vec![
+            crate::strategem::find("Reinforce").unwrap(),
+            crate::strategem::find("Resupply").unwrap(),
+        ];
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let mut game = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Loadout)
+            .pool(pool)
+            .build();
+
+        for name in take_names(&mut game.state, GameMode::Loadout, 20) {
+            assert!(name.eq("Reinforce") || name.eq("Resupply"));
+        }
+    }
+
+    #[test]
+    fn hardcore_ends_on_first_mistake() {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let mut game = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Hardcore)
+            .build();
+
+        // Reinforce starts with Up
+        *game.state.strategem_mut() = crate::strategem::find("Reinforce").unwrap();
+        game.state.strategem_mut().assert_key(StrategemKey::Down);
+        game.update_state();
+
+        assert!(game.is_over());
+    }
+
+    #[test]
+    fn runs_are_ranked_per_duration_and_difficulty() {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let long = || GameTimer::start_from(Duration::from_secs(60));
+
+        let game = Game::builder(&mut player, &mut records).build();
+        assert_eq!(None, game.leaderboard());
+
+        let game = Game::builder(&mut player, &mut records)
+            .timer(long())
+            .build();
+        assert_eq!(Some("Classic 60s"), game.leaderboard().as_deref());
+
+        let game = Game::builder(&mut player, &mut records)
+            .timer(long())
+            .mode(GameMode::Hardcore)
+            .build();
+        assert_eq!(Some("Hardcore 60s"), game.leaderboard().as_deref());
+
+        let game = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Endless)
+            .build();
+        assert_eq!(Some("Endless"), game.leaderboard().as_deref());
+
+        let game = Game::builder(&mut player, &mut records)
+            .difficulty(Difficulty::Helldive)
+            .build();
+        assert_eq!(Some("Classic Helldive"), game.leaderboard().as_deref());
+    }
+
+    #[test]
+    fn daily_runs_use_fixed_length_and_rolled_modifiers() {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+
+        let rolled = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Daily(0))
+            .build()
+            .modifiers()
+            .active()
+            .to_vec();
+        let game = Game::builder(&mut player, &mut records)
+            .timer(GameTimer::start_from(Duration::from_secs(60)))
+            .mode(GameMode::Daily(0))
+            .modifiers(Modifiers::new(Modifier::ALL.to_vec()))
+            .build();
+        assert_eq!(DEFAULT_DURATION, game.length);
+        assert_eq!(rolled, game.modifiers().active());
+    }
+
+    #[test]
+    fn sprint_runs_use_default_difficulty_and_no_modifiers() {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+
+        let game = Game::builder(&mut player, &mut records)
+            .difficulty(Difficulty::Helldive)
+            .modifiers(Modifiers::new(vec![Modifier::IonStorm]))
+            .mode(GameMode::Sprint(SPRINT_LENGTHS[0]))
+            .build();
+        assert_eq!(Difficulty::default(), game.difficulty());
+        assert!(game.modifiers().active().is_empty());
+
+        let game = Game::builder(&mut player, &mut records)
+            .modifiers(Modifiers::random())
+            .mode(GameMode::Sprint(SPRINT_LENGTHS[0]))
+            .build();
+        assert!(game.modifiers().active().is_empty());
+    }
+
+    #[test]
+    fn daily_runs_ignore_rules_and_upgrades() {
+        let mut player = PlayerData::default();
+        player.set_bonus_score(50);
+        let mut records = Records::default();
+        let rules = Rules::parse("points.hard = 300").unwrap();
+
+        let game = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Daily(0))
+            .rules(rules.clone())
+            .build();
+        assert_eq!(Rules::default(), game.rules);
+        assert_eq!(0, game.upgrades.bonus_score());
+
+        let game = Game::builder(&mut player, &mut records)
+            .rules(rules.clone())
+            .build();
+        assert_eq!(rules, game.rules);
+        assert_eq!(50, game.upgrades.bonus_score());
+    }
+
+    #[test]
+    fn summary_tracks_streak_and_accuracy() {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let mut game = Game::builder(&mut player, &mut records)
+            .mode(GameMode::Endless)
+            .build();
+
+        // Resupply is Down, Down, Up, Right
+        *game.state.strategem_mut() = crate::strategem::find("Resupply").unwrap();
+        game.press(StrategemKey::Up);
+        game.state.strategem_mut().reset();
+        for key in [
+            StrategemKey::Down,
+            StrategemKey::Down,
+            StrategemKey::Up,
+            StrategemKey::Right,
+        ] {
+            game.press(key);
+        }
+        game.update_state();
+
+        let summary = game.summary();
+        assert_eq!(1, summary.best_streak);
+        assert_eq!(80.0, summary.accuracy);
+    }
+
+    #[test]
+    fn replay_reproduces_the_run() {
+        let mut player = PlayerData::default();
+        let mut records = Records::default();
+        let mut game = Game::builder(&mut player, &mut records)
+            .timer(GameTimer::start_from(Duration::from_secs(10)))
+            .seed(Some(9))
+            .modifiers(Modifiers::random())
+            .build();
+
+        // Types every code correctly except for every 7th key
+        let mut at = Duration::ZERO;
+        for i in 0..80 {
+            at += Duration::from_millis(120);
+            game.catch_up(at);
+            let Some(next) = game.state.strategem().next_key() else {
+                continue;
+            };
+            let key = match (next, i % 7 == 6) {
+                (StrategemKey::Up, false) | (StrategemKey::Down, true) => Key::ArrowUp,
+                (StrategemKey::Down, false) | (_, true) => Key::ArrowDown,
+                (StrategemKey::Left, false) => Key::ArrowLeft,
+                (StrategemKey::Right, false) => Key::ArrowRight,
+            };
+            game.handle_input(at, key).unwrap();
+        }
+        game.catch_up(Duration::from_secs(60));
+        let replay = game.replay();
+        let checkpoints = game.state.checkpoints.len();
+        assert!(game.state.cleared > 0);
+
+        let mut player = replay.player();
+        let mut records = Records::default();
+        let inputs = replay.inputs.clone();
+        let mut played = Game::builder(&mut player, &mut records)
+            .playback(replay)
+            .build();
+        played.simulate(&inputs);
+
+        assert_eq!(game.state.score, played.state.score);
+        assert_eq!(game.state.cleared, played.state.cleared);
+        assert_eq!(game.state.drawn, played.state.drawn);
+        assert_eq!(checkpoints, played.state.checkpoints.len());
+        assert_eq!(played.state.cleared, played.results().len());
+        assert_eq!(game.modifiers().active(), played.modifiers().active());
+    }
+}
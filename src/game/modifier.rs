@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::strategem::{Strategem, StrategemKey};
+
+/// Mission modifier changing the rules of a run in exchange for a higher score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Modifier {
+    /// Random keys of every code are obscured until entered
+    IonStorm,
+    /// One key of every code is swapped for another direction
+    StrategemScrambler,
+    ReducedTime,
+    IncreasedPenalty,
+}
+
+impl Modifier {
+    pub const ALL: [Modifier; 4] = [
+        Modifier::IonStorm,
+        Modifier::StrategemScrambler,
+        Modifier::ReducedTime,
+        Modifier::IncreasedPenalty,
+    ];
+
+    /// Score bonus of the modifier, in percent
+    const fn score_bonus(&self) -> u32 {
+        match self {
+            Modifier::IonStorm => 25,
+            Modifier::StrategemScrambler => 20,
+            Modifier::ReducedTime => 20,
+            Modifier::IncreasedPenalty => 15,
+        }
+    }
+
+    /// Short summary of the modifier for menus
+    pub fn describe(&self) -> String {
+        let rules = match self {
+            Modifier::IonStorm => "random keys of every code are obscured",
+            Modifier::StrategemScrambler => "one key of every code is swapped",
+            Modifier::ReducedTime => "a quarter less starting time and time rewards",
+            Modifier::IncreasedPenalty => "double time penalty for mistakes",
+        };
+
+        format!("{self} - {rules} (+{}% score)", self.score_bonus())
+    }
+}
+
+impl std::fmt::Display for Modifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Modifier::IonStorm => write!(f, "Ion Storm"),
+            Modifier::StrategemScrambler => write!(f, "Strategem Scrambler"),
+            Modifier::ReducedTime => write!(f, "Reduced Time"),
+            Modifier::IncreasedPenalty => write!(f, "Increased Penalty"),
+        }
+    }
+}
+
+/// Modifiers of a run, optionally rolled anew for every run
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Modifiers {
+    active: Vec<Modifier>,
+    random: bool,
+}
+
+impl Modifiers {
+    const MAX_ROLLED: usize = 2;
+
+    pub fn new(active: Vec<Modifier>) -> Self {
+        Self {
+            active,
+            random: false,
+        }
+    }
+
+    /// Modifiers rolled from the seed of every run
+    pub fn random() -> Self {
+        Self {
+            active: Vec::new(),
+            random: true,
+        }
+    }
+
+    pub fn active(&self) -> &[Modifier] {
+        &self.active
+    }
+
+    pub fn is_random(&self) -> bool {
+        self.random
+    }
+
+    pub fn has(&self, modifier: Modifier) -> bool {
+        self.active.contains(&modifier)
+    }
+
+    /// Turns `modifier` on or off, no longer rolling them
+    pub fn toggle(&mut self, modifier: Modifier) {
+        self.random = false;
+        match self.active.iter().position(|m| *m == modifier) {
+            Some(idx) => {
+                self.active.remove(idx);
+            }
+            None => self.active.push(modifier),
+        }
+    }
+
+    /// Rolls the modifiers of a run with `seed`, if they are random
+    pub fn roll(&mut self, seed: u64) {
+        if !self.random {
+            return;
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let count = rng.gen_range(1..=Self::MAX_ROLLED);
+        self.active = Modifier::ALL
+            .choose_multiple(&mut rng, count)
+            .copied()
+            .collect();
+    }
+
+    pub fn score(&self, score: usize) -> usize {
+        let bonus: u32 = self.active.iter().map(Modifier::score_bonus).sum();
+        score * (100 + bonus) as usize / 100
+    }
+
+    /// Part of the timer taken away at the start of a run or round
+    pub fn time_cut(&self, starting_time: Duration) -> Duration {
+        match self.has(Modifier::ReducedTime) {
+            true => starting_time / 4,
+            false => Duration::ZERO,
+        }
+    }
+
+    pub fn time_reward(&self, reward: Duration) -> Duration {
+        match self.has(Modifier::ReducedTime) {
+            true => reward * 3 / 4,
+            false => reward,
+        }
+    }
+
+    pub fn penalty(&self, penalty: Duration) -> Duration {
+        match self.has(Modifier::IncreasedPenalty) {
+            true => penalty * 2,
+            false => penalty,
+        }
+    }
+
+    /// Applies the modifiers to a newly shown strategem, `seed` picks the keys it affects.
+    /// Returns the mask of the keys to obscure
+    pub fn prepare(&self, strategem: &mut Strategem, seed: u64) -> u16 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let len = strategem.code_len();
+
+        if self.has(Modifier::StrategemScrambler) {
+            const KEYS: [StrategemKey; 4] = [
+                StrategemKey::Up,
+                StrategemKey::Down,
+                StrategemKey::Left,
+                StrategemKey::Right,
+            ];
+
+            let idx = rng.gen_range(0..len);
+            let current = strategem.keys().nth(idx).cloned();
+            let others: Vec<_> = KEYS
+                .iter()
+                .filter(|key| Some(*key) != current.as_ref())
+                .collect();
+            if let Some(key) = others.choose(&mut rng) {
+                strategem.replace_key(idx, (*key).clone());
+            }
+        }
+
+        if !self.has(Modifier::IonStorm) {
+            return 0;
+        }
+
+        (0..len)
+            .filter(|_| rng.gen_bool(1.0 / 3.0))
+            .fold(0, |mask, idx| mask | 1 << idx)
+    }
+}
+
+impl std::fmt::Display for Modifiers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.active.is_empty() {
+            return write!(f, "None");
+        }
+
+        let names = self.active.iter().map(Modifier::to_string);
+        write!(
+            f,
+            "{} (x{:.2} score)",
+            names.collect::<Vec<_>>().join(", "),
+            self.score(100) as f64 / 100.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrambler_changes_one_key() {
+        let modifiers = Modifiers::new(vec![Modifier::StrategemScrambler]);
+        let original = crate::strategem::find("Reinforce").unwrap();
+
+        for seed in 0..20 {
+            let mut scrambled = original.clone();
+            assert_eq!(0, modifiers.prepare(&mut scrambled, seed));
+
+            let changed = original
+                .keys()
+                .zip(scrambled.keys())
+                .filter(|(a, b)| a != b)
+                .count();
+            assert_eq!(1, changed);
+        }
+    }
+}
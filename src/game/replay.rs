@@ -0,0 +1,137 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    input::{self, InputSource, FRAME},
+    Difficulty, GameMode, Modifier,
+};
+use crate::{
+    error::Result, event::Key, rules::Rules, storage::PlayerData, strategem::Strategem, utility,
+};
+
+/// Recording of a run, saved to its own file in the replays directory
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    pub mode: GameMode,
+    pub seed: u64,
+    /// Milliseconds since the Unix epoch
+    pub recorded_at: u64,
+    pub difficulty: Difficulty,
+    pub modifiers: Vec<Modifier>,
+    pub rules: Rules,
+    /// Game length, before the difficulty scaled it
+    pub duration: Duration,
+    pub penalty: Duration,
+    pub time_reward: Duration,
+    pub bonus_score: usize,
+    pub pool: Vec<String>,
+    /// Names of the strategems in the order they were drawn
+    pub sequence: Vec<String>,
+    /// Key presses with the time since the start of the run they happened at
+    pub inputs: Vec<(Duration, Key)>,
+    pub score: usize,
+    pub cleared: usize,
+}
+
+impl Replay {
+    const DIR: &'static str = "replays";
+    const EXTENSION: &'static str = "replay";
+
+    fn dir() -> Result<PathBuf> {
+        let dir = utility::data_dir()?.join(Self::DIR);
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Paths of every saved replay, newest first
+    pub fn list() -> Result<Vec<PathBuf>> {
+        let mut paths = std::fs::read_dir(Self::dir()?)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == Self::EXTENSION))
+            .collect::<Vec<_>>();
+
+        paths.sort_by(|a, b| b.cmp(a));
+        Ok(paths)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(bincode::deserialize_from(File::open(path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let name = format!("{}.{}", self.recorded_at, Self::EXTENSION);
+        let file = File::create(Self::dir()?.join(name))?;
+        bincode::serialize_into(file, self)?;
+        Ok(())
+    }
+
+    pub fn pool(&self) -> Vec<Strategem> {
+        self.pool
+            .iter()
+            .filter_map(|name| crate::strategem::find(name))
+            .collect()
+    }
+
+    /// Player with the upgrades the run was recorded with
+    pub fn player(&self) -> PlayerData {
+        let mut player = PlayerData::default();
+        player.set_penalty_debuff(self.penalty.as_millis() as u64);
+        player.set_time_reward(self.time_reward.as_millis() as u64);
+        player.set_bonus_score(self.bonus_score);
+        player
+    }
+}
+
+impl std::fmt::Display for Replay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:<16} {:>6} pts {:>3} cleared",
+            utility::format_day(self.recorded_at / 86_400_000),
+            self.mode.to_string(),
+            self.score,
+            self.cleared
+        )
+    }
+}
+
+/// Feeds the key presses of a replay at the time they were recorded,
+/// any key pressed at the keyboard stops the playback
+pub struct Playback {
+    inputs: VecDeque<(Duration, Key)>,
+}
+
+impl Playback {
+    pub fn new(replay: &Replay) -> Self {
+        Self {
+            inputs: replay.inputs.iter().copied().collect(),
+        }
+    }
+}
+
+impl InputSource for Playback {
+    fn next(&mut self, started_at: Instant) -> Result<Option<(Duration, Key)>> {
+        if input::key_pressed()? {
+            return Ok(Some((started_at.elapsed(), Key::Escape)));
+        }
+
+        let now = started_at.elapsed();
+        match self.inputs.front() {
+            Some(&(at, _)) if at <= now => Ok(self.inputs.pop_front()),
+            Some(&(at, _)) => {
+                std::thread::sleep((at - now).min(FRAME));
+                Ok(None)
+            }
+            None => {
+                std::thread::sleep(FRAME);
+                Ok(None)
+            }
+        }
+    }
+}
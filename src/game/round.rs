@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+/// How long the round summary stays on screen before the next round
+pub const INTERMISSION: Duration = Duration::from_millis(2500);
+
+const FIRST_ROUND_LEN: usize = 6;
+const MAX_ROUND_LEN: usize = 16;
+
+pub struct RoundBonus {
+    pub round: usize,
+    pub time: usize,
+    pub perfect: usize,
+}
+
+impl RoundBonus {
+    pub fn total(&self) -> usize {
+        self.round + self.time + self.perfect
+    }
+
+    /// Bonus with every part scaled by `scale`
+    pub fn scaled(self, scale: impl Fn(usize) -> usize) -> Self {
+        Self {
+            round: scale(self.round),
+            time: scale(self.time),
+            perfect: scale(self.perfect),
+        }
+    }
+}
+
+pub struct Round {
+    number: usize,
+    mistakes: usize,
+}
+
+impl Round {
+    pub fn new(number: usize) -> Self {
+        Self {
+            number,
+            mistakes: 0,
+        }
+    }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// How many strategems the round's queue holds
+    pub fn queue_len(&self) -> usize {
+        (FIRST_ROUND_LEN + self.number - 1).min(MAX_ROUND_LEN)
+    }
+
+    pub fn add_mistake(&mut self) {
+        self.mistakes += 1;
+    }
+
+    /// Bonus for clearing the round with `remaining` time left on the timer
+    pub fn bonus(&self, remaining: Duration) -> RoundBonus {
+        RoundBonus {
+            round: 75 + 25 * (self.number - 1),
+            time: remaining.as_millis() as usize / 100,
+            perfect: if self.mistakes == 0 { 100 } else { 0 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_get_longer() {
+        assert_eq!(FIRST_ROUND_LEN, Round::new(1).queue_len());
+        assert_eq!(FIRST_ROUND_LEN + 2, Round::new(3).queue_len());
+        assert_eq!(MAX_ROUND_LEN, Round::new(100).queue_len());
+    }
+
+    #[test]
+    fn round_bonus() {
+        let mut round = Round::new(3);
+        let bonus = round.bonus(Duration::from_millis(12_345));
+        assert_eq!(125, bonus.round);
+        assert_eq!(123, bonus.time);
+        assert_eq!(100, bonus.perfect);
+
+        let scaled = bonus.scaled(|points| points * 2);
+        assert_eq!(250 + 246 + 200, scaled.total());
+
+        round.add_mistake();
+        assert_eq!(0, round.bonus(Duration::ZERO).perfect);
+    }
+}
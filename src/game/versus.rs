@@ -0,0 +1,249 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    error::Result,
+    event::{Controls, RoutedKey},
+    rules::Rules,
+    screenln,
+    strategem::{Strategem, StrategemKey, StrategemSource},
+    tui,
+    utility::{self, FreezeState, InputFreeze},
+};
+
+/// Strategems a player has to clear to win the race
+pub const RACE_LENGTH: usize = 20;
+
+pub(super) const COLUMN: u16 = 40;
+
+pub(super) struct Racer {
+    name: &'static str,
+    source: StrategemSource,
+    strategem: Strategem,
+    rules: Rules,
+    freeze: InputFreeze,
+    score: usize,
+    streak: usize,
+    cleared: usize,
+    mistakes: usize,
+    finished_in: Option<Duration>,
+}
+
+impl Racer {
+    pub(super) fn new(name: &'static str, seed: u64, rules: Rules) -> Self {
+        let mut source = StrategemSource::new(seed);
+
+        Self {
+            name,
+            strategem: source.next(),
+            source,
+            rules,
+            freeze: InputFreeze::new(30),
+            score: 0,
+            streak: 0,
+            cleared: 0,
+            mistakes: 0,
+            finished_in: None,
+        }
+    }
+
+    pub(super) fn score(&self) -> usize {
+        self.score
+    }
+
+    pub(super) fn cleared(&self) -> usize {
+        self.cleared
+    }
+
+    pub(super) fn finished_in(&self) -> Option<Duration> {
+        self.finished_in
+    }
+
+    /// Returns `true` if the key was a mistake
+    pub(super) fn press(&mut self, key: StrategemKey) -> bool {
+        if self.finished_in.is_some() || !self.strategem.is_valid() {
+            return false;
+        }
+
+        self.strategem.assert_key(key);
+        if !self.strategem.is_valid() {
+            self.record_mistake();
+            return true;
+        }
+
+        false
+    }
+
+    /// Returns `true` if a strategem was cleared
+    pub(super) fn update(&mut self, elapsed: Duration) -> bool {
+        if self.finished_in.is_some() {
+            return false;
+        }
+
+        if self.strategem.is_completed() {
+            self.streak += 1;
+            self.score += self.rules.score(
+                self.strategem.difficulty(),
+                self.rules.multiplier(self.streak),
+                0,
+            );
+            self.catch_up(self.cleared + 1);
+            if self.cleared == RACE_LENGTH {
+                self.finished_in = Some(elapsed);
+            }
+            return true;
+        } else if !self.strategem.is_valid() {
+            if let FreezeState::Completed = self.freeze.ping() {
+                self.strategem.reset();
+            }
+        }
+
+        false
+    }
+
+    pub(super) fn record_mistake(&mut self) {
+        self.streak = 0;
+        self.mistakes += 1;
+    }
+
+    /// Moves on to the strategem after the `cleared` first ones
+    pub(super) fn catch_up(&mut self, cleared: usize) {
+        while self.cleared < cleared.min(RACE_LENGTH) {
+            self.cleared += 1;
+            if self.cleared < RACE_LENGTH {
+                self.strategem = self.source.next();
+            }
+        }
+    }
+
+    /// Mirrors a remote racer's progress
+    pub(super) fn sync(&mut self, cleared: usize, score: usize) {
+        self.catch_up(cleared);
+        self.score = score;
+    }
+
+    pub(super) fn finish(&mut self, time: Duration) {
+        self.finished_in = Some(time);
+    }
+
+    pub(super) fn status(&self) -> String {
+        let current = match self.finished_in {
+            Some(time) => format!("Finished in {}\n", utility::format_duration(time)),
+            None => self.strategem.to_string(),
+        };
+
+        format!(
+            "{}\nScore: {}\nCleared: {}/{}\nMistakes: {}\n{}",
+            self.name, self.score, self.cleared, RACE_LENGTH, self.mistakes, current
+        )
+    }
+}
+
+/// Two players race through the same strategem sequence on one keyboard
+pub struct Versus {
+    racers: [Racer; 2],
+    controls: [Controls; 2],
+    started_at: Instant,
+    is_running: bool,
+}
+
+impl Versus {
+    pub fn new(seed: u64, rules: Rules) -> Self {
+        Self {
+            racers: [
+                Racer::new("Player 1 (WASD)", seed, rules.clone()),
+                Racer::new("Player 2 (Arrows)", seed, rules),
+            ],
+            controls: [Controls::wasd(), Controls::arrows()],
+            started_at: Instant::now(),
+            is_running: true,
+        }
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        tui::screen::full_clear()?;
+
+        while self.is_running {
+            if crossterm::event::poll(Duration::from_millis(17))? {
+                self.handle_input()?;
+            } else {
+                self.print_frame()?;
+                self.update_state();
+
+                if self.winner().is_some() {
+                    return self.handle_race_over();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_input(&mut self) -> Result<()> {
+        match crate::event::read_routed(&self.controls)? {
+            Some(RoutedKey::Escape) => {
+                tui::screen::clear()?;
+                self.is_running = false;
+            }
+            Some(RoutedKey::Player(idx, key)) => {
+                self.racers[idx].press(key.into());
+            }
+            None => (),
+        }
+
+        Ok(())
+    }
+
+    fn print_frame(&mut self) -> Result<()> {
+        screenln!(
+            "\x1b[KFirst to clear {RACE_LENGTH} strategems wins! Elapsed: {}",
+            utility::format_duration(self.started_at.elapsed())
+        )?;
+        tui::columns(&self.racers[0].status(), &self.racers[1].status(), COLUMN)?;
+        screenln!("\x1b[K{}: {}", self.racers[0].name, self.controls[0])?;
+        screenln!("\x1b[K{}: {}", self.racers[1].name, self.controls[1])?;
+
+        tui::screen::move_back()
+    }
+
+    fn update_state(&mut self) {
+        let elapsed = self.started_at.elapsed();
+        self.racers.iter_mut().for_each(|r| {
+            r.update(elapsed);
+        });
+    }
+
+    fn winner(&self) -> Option<&Racer> {
+        self.racers
+            .iter()
+            .filter(|r| r.finished_in.is_some())
+            .min_by_key(|r| r.finished_in)
+    }
+
+    fn handle_race_over(&mut self) -> Result<()> {
+        tui::screen::clear()?;
+
+        if let Some(winner) = self.winner() {
+            screenln!("{} wins the race!", winner.name)?;
+        }
+        tui::columns(&self.racers[0].status(), &self.racers[1].status(), COLUMN)?;
+
+        tui::confirm_quit(Some("return to game modes"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn racers_share_the_sequence() {
+        let mut versus = Versus::new(42, Rules::default());
+        let [first, second] = &mut versus.racers;
+
+        for _ in 0..10 {
+            assert_eq!(first.strategem.name(), second.strategem.name());
+            first.strategem = first.source.next();
+            second.strategem = second.source.next();
+        }
+    }
+}
@@ -5,8 +5,10 @@ mod app;
 mod error;
 mod event;
 mod game;
+mod rules;
 mod storage;
 mod strategem;
+mod training;
 mod tui;
 mod utility;
 
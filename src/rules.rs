@@ -0,0 +1,294 @@
+use std::{fmt::Display, fs::File, io::Write, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Result,
+    strategem::StrategemDifficulty,
+    utility::{self, Multiplier},
+};
+
+/// Scoring rules of a run, read from the rules file in the data directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rules {
+    /// Points for a cleared easy, medium and hard strategem
+    points: [usize; 3],
+    /// Multiplier tiers in streak order, the first one starting at no streak
+    tiers: Vec<Tier>,
+    time_reward: Duration,
+    /// Least time reward endless runs go down to
+    min_time_reward: Duration,
+    penalty: Duration,
+    /// Idle time after which the multiplier drops a tier, zero if it never does
+    decay: Duration,
+    /// Bonus points for a strategem cleared the moment it appeared
+    speed_bonus: usize,
+    /// Time per key of a code at which the speed bonus runs out
+    speed_pace: Duration,
+}
+
+/// Score multiplier reached once `streak` strategems were cleared in a row
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Tier {
+    streak: usize,
+    multiplier: f64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            points: [50, 75, 100],
+            tiers: [(0, 1.0), (6, 2.0), (21, 2.5), (41, 3.0), (71, 4.0)]
+                .map(|(streak, multiplier)| Tier { streak, multiplier })
+                .to_vec(),
+            time_reward: Duration::from_millis(1000),
+            min_time_reward: Duration::from_millis(200),
+            penalty: Duration::from_millis(1000),
+            decay: Duration::from_secs(5),
+            speed_bonus: 50,
+            speed_pace: Duration::from_millis(500),
+        }
+    }
+}
+
+impl Rules {
+    const FILENAME: &'static str = "rules.txt";
+
+    /// Reads the rules file, writing one with the defaults if there is none
+    pub fn open() -> Result<Self> {
+        let path = utility::data_dir()?.join(Self::FILENAME);
+        if !std::fs::exists(&path)? {
+            let rules = Self::default();
+            File::create(&path)?.write_all(rules.to_string().as_bytes())?;
+            return Ok(rules);
+        }
+
+        Self::parse(&std::fs::read_to_string(&path)?)
+            .map_err(|err| format!("{}: {err}", path.display()).into())
+    }
+
+    /// Parses `key = value` lines, keys that are left out keep their default.
+    /// Tiers given in the file replace all of the default ones
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut rules = Self::default();
+        let mut tiers = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || format!("Invalid rule on line {}: '{line}'", i + 1);
+            let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+            let value = value.trim();
+            let seconds = || {
+                let secs = value.parse().map_err(|_| invalid())?;
+                Duration::try_from_secs_f64(secs).map_err(|_| invalid())
+            };
+
+            match key.trim() {
+                "points.easy" => rules.points[0] = value.parse().map_err(|_| invalid())?,
+                "points.medium" => rules.points[1] = value.parse().map_err(|_| invalid())?,
+                "points.hard" => rules.points[2] = value.parse().map_err(|_| invalid())?,
+                "time_reward" => rules.time_reward = seconds()?,
+                "min_time_reward" => rules.min_time_reward = seconds()?,
+                "penalty" => rules.penalty = seconds()?,
+                "decay" => rules.decay = seconds()?,
+                "speed_bonus" => rules.speed_bonus = value.parse().map_err(|_| invalid())?,
+                "speed_pace" => rules.speed_pace = seconds()?,
+                key => {
+                    let streak = key.strip_prefix("tier.").ok_or_else(invalid)?;
+                    tiers.push(Tier {
+                        streak: streak.parse().map_err(|_| invalid())?,
+                        multiplier: value.parse().map_err(|_| invalid())?,
+                    });
+                }
+            }
+        }
+
+        if !tiers.is_empty() {
+            tiers.sort_by_key(|tier| tier.streak);
+            if tiers[0].streak != 0 || tiers.windows(2).any(|w| w[0].streak == w[1].streak) {
+                return Err("Tiers need distinct streaks, the first one being 0".into());
+            }
+            rules.tiers = tiers;
+        }
+
+        Ok(rules)
+    }
+
+    /// Multiplier tier reached with `streak`
+    pub fn multiplier(&self, streak: usize) -> Multiplier {
+        let idx = self.tier_idx(streak);
+        let tier = self.tiers[idx];
+        let progress = self
+            .tiers
+            .get(idx + 1)
+            .map(|next| (streak - tier.streak, next.streak - tier.streak));
+
+        Multiplier::new(idx, tier.multiplier, progress)
+    }
+
+    /// Streak left after idling for `idle`, dropped to the start of the tier below
+    /// once it exceeds the decay time
+    pub fn decay(&self, streak: usize, idle: Duration) -> usize {
+        if self.decay.is_zero() || idle < self.decay {
+            return streak;
+        }
+
+        match self.tier_idx(streak) {
+            0 => 0,
+            idx => self.tiers[idx - 1].streak,
+        }
+    }
+
+    fn tier_idx(&self, streak: usize) -> usize {
+        self.tiers
+            .iter()
+            .rposition(|tier| tier.streak <= streak)
+            .unwrap_or(0)
+    }
+
+    /// Score of a cleared strategem
+    pub fn score(
+        &self,
+        difficulty: &StrategemDifficulty,
+        multiplier: Multiplier,
+        bonus: usize,
+    ) -> usize {
+        let points = match difficulty {
+            StrategemDifficulty::Easy => self.points[0],
+            StrategemDifficulty::Medium => self.points[1],
+            StrategemDifficulty::Hard => self.points[2],
+        };
+
+        (points as f64 * multiplier.value()).round() as usize + bonus
+    }
+
+    /// Bonus for a strategem cleared `time` after it first appeared,
+    /// going down linearly with the time taken per key of its code
+    pub fn speed_bonus(&self, time: Duration, code_len: usize) -> usize {
+        if self.speed_pace.is_zero() {
+            return 0;
+        }
+
+        let per_key = time.as_secs_f64() / code_len.max(1) as f64;
+        let pace = 1.0 - per_key / self.speed_pace.as_secs_f64();
+        (self.speed_bonus as f64 * pace.max(0.0)).round() as usize
+    }
+
+    pub fn time_reward(&self) -> Duration {
+        self.time_reward
+    }
+
+    pub fn min_time_reward(&self) -> Duration {
+        self.min_time_reward
+    }
+
+    /// Time penalty for a mistake, `penalty` is the player's own penalty
+    /// which is relative to the built-in one
+    pub fn penalty(&self, penalty: Duration) -> Duration {
+        self.penalty
+            .mul_f64(penalty.as_secs_f64() / Self::default().penalty.as_secs_f64())
+    }
+}
+
+impl Display for Rules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "# Scoring rules, delete this file to restore the defaults"
+        )?;
+        writeln!(f)?;
+        writeln!(f, "# Points for a cleared strategem of every difficulty")?;
+        writeln!(f, "points.easy = {}", self.points[0])?;
+        writeln!(f, "points.medium = {}", self.points[1])?;
+        writeln!(f, "points.hard = {}", self.points[2])?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "# Score multiplier reached after clearing tier.<streak> strategems in a row"
+        )?;
+        for tier in &self.tiers {
+            writeln!(f, "tier.{} = {}", tier.streak, tier.multiplier)?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "# Seconds without a key press after which the multiplier drops a tier, 0 to never drop"
+        )?;
+        writeln!(f, "decay = {}", self.decay.as_secs_f64())?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "# Bonus points for clearing a strategem right away, running out at speed_pace seconds per key"
+        )?;
+        writeln!(f, "speed_bonus = {}", self.speed_bonus)?;
+        writeln!(f, "speed_pace = {}", self.speed_pace.as_secs_f64())?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "# Seconds added for a cleared strategem, and the least endless runs go down to"
+        )?;
+        writeln!(f, "time_reward = {}", self.time_reward.as_secs_f64())?;
+        writeln!(
+            f,
+            "min_time_reward = {}",
+            self.min_time_reward.as_secs_f64()
+        )?;
+        writeln!(f)?;
+        writeln!(f, "# Seconds taken away after a mistake")?;
+        writeln!(f, "penalty = {}", self.penalty.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_file_round_trips() {
+        let defaults = Rules::default();
+        assert_eq!(defaults, Rules::parse(&defaults.to_string()).unwrap());
+
+        let custom =
+            Rules::parse("points.hard = 300\ntier.0 = 1\ntier.3 = 5\npenalty = 2.5").unwrap();
+        assert_eq!(
+            1500,
+            custom.score(&StrategemDifficulty::Hard, custom.multiplier(3), 0)
+        );
+        assert_eq!(
+            Duration::from_millis(2500),
+            custom.penalty(defaults.penalty)
+        );
+
+        assert!(Rules::parse("tier.5 = 2").is_err());
+        assert!(Rules::parse("points.legendary = 500").is_err());
+    }
+
+    #[test]
+    fn streak_decays_a_tier_at_a_time() {
+        let rules = Rules::default();
+        let idle = Duration::from_secs(5);
+
+        assert_eq!(4.0, rules.multiplier(100).value());
+        assert_eq!(Multiplier::new(2, 2.5, Some((4, 20))), rules.multiplier(25));
+        assert_eq!(Multiplier::new(4, 4.0, None), rules.multiplier(71));
+
+        assert_eq!(25, rules.decay(25, idle - Duration::from_millis(1)));
+        assert_eq!(6, rules.decay(25, idle));
+        assert_eq!(0, rules.decay(6, idle));
+        assert_eq!(0, rules.decay(3, idle));
+    }
+
+    #[test]
+    fn speed_bonus_is_paced_per_key() {
+        let rules = Rules::default();
+
+        assert_eq!(50, rules.speed_bonus(Duration::ZERO, 4));
+        assert_eq!(25, rules.speed_bonus(Duration::from_secs(1), 4));
+        assert_eq!(0, rules.speed_bonus(Duration::from_secs(1), 2));
+        assert_eq!(25, rules.speed_bonus(Duration::from_millis(2000), 8));
+    }
+}
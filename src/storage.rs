@@ -7,7 +7,11 @@ use std::{
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::{error::Result, utility};
+use crate::{
+    error::Result,
+    game::{Difficulty, Modifiers, DEFAULT_DURATION},
+    utility,
+};
 
 pub trait Storage
 where
@@ -41,7 +45,7 @@ where
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerData {
     wallet: usize,
     penalty_debuff_millis: u64,
@@ -102,7 +106,7 @@ impl Storage for PlayerData {
     const FILENAME: &'static str = "player_data";
 }
 
-#[derive(serde::Deserialize, serde::Serialize)]
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
 pub struct Leaderboard(pub BTreeMap<String, usize>);
 
 impl Storage for Leaderboard {
@@ -128,8 +132,8 @@ impl Leaderboard {
         self.0.insert(nickname.to_string(), score);
     }
 
-    pub fn iter(&self) -> Iter<String, usize> {
-        self.0.iter()
+    pub fn get(&self, nickname: &str) -> Option<usize> {
+        self.0.get(nickname).copied()
     }
 
     pub fn sorted_vec(&self) -> Vec<(&String, &usize)> {
@@ -139,6 +143,237 @@ impl Leaderboard {
     }
 }
 
+/// Leaderboards of runs played with non-classic rules, keyed by board name
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct Leaderboards(BTreeMap<String, Leaderboard>);
+
+impl Storage for Leaderboards {
+    const FILENAME: &'static str = "leaderboards";
+}
+
+impl Leaderboards {
+    pub fn get(&self, name: &str) -> Option<&Leaderboard> {
+        self.0.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> &mut Leaderboard {
+        self.0.entry(name.to_string()).or_default()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.0.keys()
+    }
+}
+
+/// Fastest sprint times in milliseconds, grouped by sprint length
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct SprintLeaderboard(BTreeMap<usize, BTreeMap<String, u64>>);
+
+impl Storage for SprintLeaderboard {
+    const FILENAME: &'static str = "sprint_leaderboard";
+}
+
+impl Default for SprintLeaderboard {
+    fn default() -> Self {
+        let mut map = BTreeMap::new();
+
+        for target in crate::game::SPRINT_LENGTHS {
+            let per_strategem = [
+                ("John Helldiver", 1800),
+                ("Eagle-1", 2200),
+                ("Pelican-1", 2600),
+                ("Democracy Officer", 3100),
+            ];
+            let board = per_strategem
+                .into_iter()
+                .map(|(name, millis)| (name.to_string(), millis * target as u64))
+                .collect();
+            map.insert(target, board);
+        }
+
+        Self(map)
+    }
+}
+
+impl SprintLeaderboard {
+    pub fn insert(&mut self, target: usize, nickname: &str, millis: u64) {
+        self.0
+            .entry(target)
+            .or_default()
+            .insert(nickname.to_string(), millis);
+    }
+
+    pub fn best(&self, target: usize, nickname: &str) -> Option<u64> {
+        self.0.get(&target)?.get(nickname).copied()
+    }
+
+    pub fn sorted_vec(&self, target: usize) -> Vec<(&String, &u64)> {
+        let mut v = Vec::from_iter(self.0.get(&target).into_iter().flatten());
+        v.sort_by(|a, b| a.1.cmp(b.1));
+        v
+    }
+}
+
+/// Ranked daily challenge scores, keyed by days since the Unix epoch
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct DailyResults(BTreeMap<u64, usize>);
+
+impl Storage for DailyResults {
+    const FILENAME: &'static str = "daily_results";
+}
+
+impl DailyResults {
+    pub fn get(&self, day: u64) -> Option<usize> {
+        self.0.get(&day).copied()
+    }
+
+    pub fn insert(&mut self, day: u64, score: usize) {
+        self.0.insert(day, score);
+    }
+
+    pub fn recent(&self, count: usize) -> Vec<(u64, usize)> {
+        self.0
+            .iter()
+            .rev()
+            .take(count)
+            .map(|(day, score)| (*day, *score))
+            .collect()
+    }
+}
+
+/// Named loadouts, each holding strategem names
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct Loadouts(BTreeMap<String, Vec<String>>);
+
+impl Storage for Loadouts {
+    const FILENAME: &'static str = "loadouts";
+}
+
+impl Loadouts {
+    pub const MAX_STRATEGEMS: usize = 4;
+
+    pub fn insert(&mut self, name: &str, strategems: Vec<String>) {
+        self.0.insert(name.to_string(), strategems);
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.0.remove(name);
+    }
+
+    pub fn iter(&self) -> Iter<'_, String, Vec<String>> {
+        self.0.iter()
+    }
+}
+
+/// Hits and misses of every strategem in recall training, keyed by name
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+pub struct RecallResults(BTreeMap<String, (usize, usize)>);
+
+impl Storage for RecallResults {
+    const FILENAME: &'static str = "recall_results";
+}
+
+impl RecallResults {
+    pub fn record(&mut self, name: &str, hit: bool) {
+        let rec = self.0.entry(name.to_string()).or_default();
+        if hit {
+            rec.0 += 1;
+        } else {
+            rec.1 += 1;
+        }
+    }
+
+    pub fn get(&self, name: &str) -> (usize, usize) {
+        self.0.get(name).copied().unwrap_or_default()
+    }
+}
+
+/// Preferences chosen on the settings screen
+#[derive(Serialize, Deserialize)]
+pub struct Settings {
+    duration_secs: u64,
+    difficulty: Difficulty,
+    modifiers: Modifiers,
+}
+
+impl Storage for Settings {
+    const FILENAME: &'static str = "settings";
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            duration_secs: DEFAULT_DURATION.as_secs(),
+            difficulty: Difficulty::default(),
+            modifiers: Modifiers::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Run lengths offered on the settings screen, in seconds
+    pub const DURATIONS: [u64; 4] = [30, 60, 90, 120];
+
+    /// Time a run starts with
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.duration_secs)
+    }
+
+    pub fn set_duration(&mut self, secs: u64) {
+        self.duration_secs = secs;
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+    }
+
+    /// Modifiers every run starts with
+    pub fn modifiers(&self) -> &Modifiers {
+        &self.modifiers
+    }
+
+    pub fn modifiers_mut(&mut self) -> &mut Modifiers {
+        &mut self.modifiers
+    }
+}
+
+/// Result stores that a finished run can write to
+#[derive(Default)]
+pub struct Records {
+    pub leaderboard: Leaderboard,
+    pub boards: Leaderboards,
+    pub sprint: SprintLeaderboard,
+    pub daily: DailyResults,
+}
+
+impl Records {
+    pub fn open() -> Result<Self> {
+        Ok(Self {
+            leaderboard: Leaderboard::open()?,
+            boards: Leaderboards::open()?,
+            sprint: SprintLeaderboard::open()?,
+            daily: DailyResults::open()?,
+        })
+    }
+
+    /// Leaderboard named `board`, `None` is the classic leaderboard
+    pub fn leaderboard_mut(&mut self, board: Option<&str>) -> &mut Leaderboard {
+        match board {
+            Some(name) => self.boards.get_mut(name),
+            None => &mut self.leaderboard,
+        }
+    }
+
+    pub fn save_leaderboards(&self) -> Result<()> {
+        self.leaderboard.save()?;
+        self.boards.save()
+    }
+}
+
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct Upgrades(Vec<UpgradeItem>);
 
@@ -239,7 +474,7 @@ impl std::fmt::Display for UpgradeItem {
 
 #[cfg(test)]
 mod tests {
-    use super::Leaderboard;
+    use super::{Leaderboard, SprintLeaderboard};
 
     #[test]
     fn leaderboard_replace() {
@@ -247,4 +482,15 @@ mod tests {
         leaderboard.insert("You", 1000);
         assert_eq!(Some(&1000), leaderboard.0.get("You"));
     }
+
+    #[test]
+    fn sprint_leaderboard_sorted_fastest_first() {
+        let mut leaderboard = SprintLeaderboard::default();
+        leaderboard.insert(20, "You", 1000);
+
+        let sorted = leaderboard.sorted_vec(20);
+        assert_eq!("You", sorted[0].0);
+        assert_eq!(Some(1000), leaderboard.best(20, "You"));
+        assert_eq!(None, leaderboard.best(50, "You"));
+    }
 }
@@ -1,13 +1,14 @@
 use std::fmt::Display;
 
 use crossterm::style::Stylize;
-use rand::Rng;
 
 use crate::{event::Key, strategem::builder::StrategemBuilder, utility::format_strategem_name};
-use collections::ALL_STRATEGEMS;
+pub use collections::ALL_STRATEGEMS;
+pub use source::StrategemSource;
 
 mod builder;
 mod collections;
+mod source;
 
 pub type StrategemCode = [Option<StrategemKey>; 16];
 
@@ -31,6 +32,17 @@ impl From<Key> for StrategemKey {
     }
 }
 
+impl From<StrategemKey> for Key {
+    fn from(value: StrategemKey) -> Self {
+        match value {
+            StrategemKey::Up => Key::ArrowUp,
+            StrategemKey::Down => Key::ArrowDown,
+            StrategemKey::Left => Key::ArrowLeft,
+            StrategemKey::Right => Key::ArrowRight,
+        }
+    }
+}
+
 impl Display for StrategemKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -57,6 +69,26 @@ pub enum StrategemClass {
     Offensive,
 }
 
+impl StrategemClass {
+    pub const ALL: [StrategemClass; 4] = [
+        StrategemClass::Supply,
+        StrategemClass::Mission,
+        StrategemClass::Defensive,
+        StrategemClass::Offensive,
+    ];
+}
+
+impl Display for StrategemClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Supply => write!(f, "Supply"),
+            Self::Mission => write!(f, "Mission"),
+            Self::Defensive => write!(f, "Defensive"),
+            Self::Offensive => write!(f, "Offensive"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Strategem {
     name: &'static str,
@@ -73,7 +105,7 @@ impl Strategem {
         StrategemBuilder::new(class)
     }
 
-    pub const fn name(&self) -> &str {
+    pub const fn name(&self) -> &'static str {
         self.name
     }
 
@@ -85,6 +117,14 @@ impl Strategem {
         &self.class
     }
 
+    pub fn keys(&self) -> impl Iterator<Item = &StrategemKey> {
+        self.code.iter().map_while(Option::as_ref)
+    }
+
+    pub fn code_len(&self) -> usize {
+        self.keys().count()
+    }
+
     pub fn assert_key(&mut self, key: StrategemKey) {
         if self.is_completed() || !self.is_valid() {
             return;
@@ -96,6 +136,32 @@ impl Strategem {
         }
     }
 
+    /// Key expected next, `None` once completed or after a wrong input
+    pub fn next_key(&self) -> Option<StrategemKey> {
+        if self.valid {
+            self.code[self.idx].clone()
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the key at `idx` of the code
+    pub fn replace_key(&mut self, idx: usize, key: StrategemKey) {
+        if self.code[idx].is_some() {
+            self.code[idx] = Some(key);
+        }
+    }
+
+    /// Display of the strategem that only reveals the keys entered so far
+    pub fn masked(&self) -> Masked<'_> {
+        self.obscured(u16::MAX)
+    }
+
+    /// Display of the strategem hiding the keys set in `hidden` until they are entered
+    pub fn obscured(&self, hidden: u16) -> Masked<'_> {
+        Masked(self, hidden)
+    }
+
     pub const fn is_valid(&self) -> bool {
         self.valid
     }
@@ -134,6 +200,84 @@ impl Display for Strategem {
     }
 }
 
-pub fn random() -> Strategem {
-    ALL_STRATEGEMS[rand::thread_rng().gen::<usize>() % ALL_STRATEGEMS.len()].clone()
+pub struct Masked<'a>(&'a Strategem, u16);
+
+impl Display for Masked<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let strategem = self.0;
+        writeln!(f, "\x1b[K{}", format_strategem_name(strategem))?;
+
+        strategem.code.iter().enumerate().for_each(|(i, code)| {
+            match code {
+                Some(key) if i < strategem.idx && !strategem.is_valid() => {
+                    write!(f, "{} ", key.to_string().dark_red()).unwrap()
+                }
+                Some(key) if i < strategem.idx => {
+                    write!(f, "{} ", key.to_string().yellow()).unwrap()
+                }
+                Some(_) if self.1 & 1 << i != 0 => write!(f, "  ").unwrap(),
+                Some(key) if !strategem.is_valid() => {
+                    write!(f, "{} ", key.to_string().dark_red()).unwrap()
+                }
+                Some(key) => write!(f, "{key} ").unwrap(),
+                None => write!(f, " ").unwrap(),
+            };
+        });
+
+        Ok(())
+    }
+}
+
+pub fn find(name: &str) -> Option<Strategem> {
+    ALL_STRATEGEMS.iter().find(|s| s.name.eq(name)).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Line of a strategem display with its code
+    fn code_line(display: impl Display) -> String {
+        display.to_string().lines().nth(1).unwrap().to_string()
+    }
+
+    #[test]
+    fn masked_codes_reveal_entered_keys() {
+        let mut strategem = find("Reinforce").unwrap();
+        let keys: Vec<_> = strategem.keys().cloned().collect();
+        let hidden = |count: usize| "  ".repeat(count);
+        let padding = " ".repeat(16 - keys.len());
+
+        assert_eq!(
+            format!("{}{padding}", hidden(keys.len())),
+            code_line(strategem.masked())
+        );
+
+        strategem.assert_key(keys[0].clone());
+        let typed = format!("{} ", keys[0].to_string().yellow());
+        assert_eq!(
+            format!("{typed}{}{padding}", hidden(keys.len() - 1)),
+            code_line(strategem.masked())
+        );
+
+        let shown: String = keys[2..].iter().map(|key| format!("{key} ")).collect();
+        assert_eq!(
+            format!("{typed}{}{shown}{padding}", hidden(1)),
+            code_line(strategem.obscured(0b10))
+        );
+
+        let wrong = [StrategemKey::Up, StrategemKey::Down]
+            .into_iter()
+            .find(|key| *key != keys[1])
+            .unwrap();
+        strategem.assert_key(wrong);
+        let red: String = keys[..2]
+            .iter()
+            .map(|key| format!("{} ", key.to_string().dark_red()))
+            .collect();
+        assert_eq!(
+            format!("{red}{}{padding}", hidden(keys.len() - 2)),
+            code_line(strategem.masked())
+        );
+    }
 }
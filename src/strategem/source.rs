@@ -0,0 +1,63 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::{Strategem, StrategemDifficulty, ALL_STRATEGEMS};
+
+/// Seeded source of strategems, optionally restricted to a pool
+pub struct StrategemSource {
+    seed: u64,
+    rng: StdRng,
+    pool: Vec<Strategem>,
+}
+
+impl StrategemSource {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            pool: ALL_STRATEGEMS.to_vec(),
+        }
+    }
+
+    pub fn random() -> Self {
+        Self::new(rand::thread_rng().gen())
+    }
+
+    /// Restricts the source to `pool`, an empty pool keeps the whole catalog
+    pub fn with_pool(mut self, pool: Vec<Strategem>) -> Self {
+        if !pool.is_empty() {
+            self.pool = pool;
+        }
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restarts the sequence from `seed`, keeping the pool
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn next(&mut self) -> Strategem {
+        self.pool[self.rng.gen_range(0..self.pool.len())].clone()
+    }
+
+    /// Picks a strategem, drawing only from hard ones with `hard_bias` probability
+    pub fn next_biased(&mut self, hard_bias: f64) -> Strategem {
+        if self.rng.gen_bool(hard_bias.clamp(0.0, 1.0)) {
+            let hard = self
+                .pool
+                .iter()
+                .filter(|s| s.difficulty == StrategemDifficulty::Hard)
+                .collect::<Vec<_>>();
+
+            if let Some(strategem) = hard.choose(&mut self.rng) {
+                return (*strategem).clone();
+            }
+        }
+
+        self.next()
+    }
+}
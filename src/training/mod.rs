@@ -0,0 +1,3 @@
+pub mod practice;
+pub mod quiz;
+pub mod recall;
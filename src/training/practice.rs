@@ -0,0 +1,142 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    error::Result,
+    event::{Controls, Key},
+    screenln,
+    strategem::{Strategem, StrategemSource},
+    tui,
+    utility::{FreezeState, InputFreeze},
+};
+
+struct Attempt {
+    code_len: usize,
+    keystrokes: usize,
+    elapsed: Duration,
+}
+
+impl Attempt {
+    fn accuracy(&self) -> f64 {
+        self.code_len as f64 / self.keystrokes.max(1) as f64 * 100.0
+    }
+}
+
+pub struct Practice {
+    label: String,
+    source: StrategemSource,
+    strategem: Strategem,
+    controls: Controls,
+    freeze: InputFreeze,
+    started_at: Instant,
+    keystrokes: usize,
+    attempts: Vec<Attempt>,
+    is_running: bool,
+}
+
+impl Practice {
+    pub fn new(label: impl Into<String>, pool: Vec<Strategem>, controls: Controls) -> Self {
+        let mut source = StrategemSource::random().with_pool(pool);
+
+        Self {
+            label: label.into(),
+            strategem: source.next(),
+            source,
+            controls,
+            freeze: InputFreeze::new(30),
+            started_at: Instant::now(),
+            keystrokes: 0,
+            attempts: Vec::new(),
+            is_running: true,
+        }
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        tui::screen::full_clear()?;
+
+        while self.is_running {
+            if crossterm::event::poll(Duration::from_millis(17))? {
+                self.handle_input()?;
+            } else {
+                self.print_frame()?;
+                self.update_state();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_input(&mut self) -> Result<()> {
+        match crate::event::read(&self.controls)? {
+            Some(Key::Escape) => {
+                tui::screen::clear()?;
+                self.is_running = false;
+            }
+            Some(key) => {
+                if self.strategem.is_valid() && !self.strategem.is_completed() {
+                    self.keystrokes += 1;
+                }
+                self.strategem.assert_key(key.into());
+            }
+
+            _ => (),
+        };
+
+        Ok(())
+    }
+
+    fn print_frame(&mut self) -> Result<()> {
+        screenln!(
+            "\x1b[KPractice: {} (attempts: {})",
+            self.label,
+            self.attempts.len()
+        )?;
+        screenln!("{}", self.strategem)?;
+
+        match self.attempts.last() {
+            Some(last) => screenln!(
+                "\x1b[KLast:    {:>6.2}s {:>5.1}% accuracy",
+                last.elapsed.as_secs_f64(),
+                last.accuracy()
+            )?,
+            None => screenln!("\x1b[KLast:    -")?,
+        }
+
+        if self.attempts.is_empty() {
+            screenln!("\x1b[KAverage: -")?;
+        } else {
+            let count = self.attempts.len() as f64;
+            let time = self
+                .attempts
+                .iter()
+                .map(|a| a.elapsed.as_secs_f64())
+                .sum::<f64>();
+            let accuracy = self.attempts.iter().map(Attempt::accuracy).sum::<f64>();
+            screenln!(
+                "\x1b[KAverage: {:>6.2}s {:>5.1}% accuracy",
+                time / count,
+                accuracy / count
+            )?;
+        }
+
+        screenln!("Controls: {}", self.controls)?;
+
+        tui::screen::move_back()
+    }
+
+    fn update_state(&mut self) {
+        if self.strategem.is_completed() {
+            self.attempts.push(Attempt {
+                code_len: self.strategem.code_len(),
+                keystrokes: self.keystrokes,
+                elapsed: self.started_at.elapsed(),
+            });
+            self.strategem = self.source.next();
+            self.keystrokes = 0;
+            self.started_at = Instant::now();
+        } else if !self.strategem.is_valid() {
+            if let FreezeState::Completed = self.freeze.ping() {
+                self.strategem.reset();
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use rand::seq::SliceRandom;
+
+use crate::{
+    error::Result,
+    screenln,
+    strategem::{Strategem, StrategemSource, ALL_STRATEGEMS},
+    tui::{self, menu::Menu},
+};
+
+const OPTIONS: usize = 4;
+
+pub struct Quiz {
+    questions: usize,
+    answered: usize,
+    correct: usize,
+    missed: Vec<Strategem>,
+}
+
+impl Quiz {
+    pub fn new(questions: usize) -> Self {
+        Self {
+            questions,
+            answered: 0,
+            correct: 0,
+            missed: Vec::new(),
+        }
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        let mut source = StrategemSource::random();
+        let mut feedback = String::new();
+
+        while self.answered < self.questions {
+            let _sc = tui::screen::cleaner();
+            tui::screen::full_clear()?;
+
+            let answer = source.next();
+            let options = Self::options(&answer);
+
+            screenln!("{feedback}")?;
+            screenln!("Question {}/{}", self.answered + 1, self.questions)?;
+            screenln!("{}", format_code(&answer))?;
+
+            let mut menu = Menu::builder();
+            for option in options.iter() {
+                menu = menu.add_item(option.name());
+            }
+
+            match menu.build().exec("Which strategem is this?")? {
+                Some(idx) if options[idx].name().eq(answer.name()) => {
+                    self.correct += 1;
+                    feedback = String::from("Correct!");
+                }
+                Some(_) => {
+                    feedback = format!("Wrong, it was {}", answer.name());
+                    self.missed.push(answer);
+                }
+                None => break,
+            }
+
+            self.answered += 1;
+        }
+
+        self.print_summary()
+    }
+
+    /// The answer and other strategems of the same class, shuffled
+    fn options(answer: &Strategem) -> Vec<Strategem> {
+        let pool = ALL_STRATEGEMS
+            .iter()
+            .filter(|s| s.class().eq(answer.class()))
+            .cloned()
+            .collect();
+        let mut source = StrategemSource::random().with_pool(pool);
+        let mut options = vec![answer.clone()];
+
+        while options.len() < OPTIONS {
+            let option = source.next();
+            if options.iter().all(|s| s.name().ne(option.name())) {
+                options.push(option);
+            }
+        }
+
+        options.shuffle(&mut rand::thread_rng());
+        options
+    }
+
+    fn print_summary(&self) -> Result<()> {
+        let _sc = tui::screen::cleaner();
+        tui::screen::full_clear()?;
+
+        let accuracy = self.correct as f64 / self.answered.max(1) as f64 * 100.0;
+        screenln!(
+            "Quiz complete! {}/{} correct ({:.0}%)",
+            self.correct,
+            self.answered,
+            accuracy
+        )?;
+
+        if !self.missed.is_empty() {
+            screenln!("Missed codes:")?;
+            self.missed.iter().for_each(|strategem| {
+                screenln!("  {:<32} {}", strategem.name(), format_code(strategem)).unwrap()
+            });
+        }
+
+        tui::confirm_quit(Some("return to training"))
+    }
+}
+
+fn format_code(strategem: &Strategem) -> String {
+    strategem
+        .keys()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
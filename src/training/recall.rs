@@ -0,0 +1,183 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::Result,
+    event::{Controls, Key},
+    screenln,
+    storage::{RecallResults, Storage},
+    strategem::{Strategem, StrategemSource},
+    tui,
+};
+
+/// How long the correct code stays on screen after a miss
+const REVEAL: Duration = Duration::from_millis(1500);
+
+pub struct Recall<'a> {
+    results: &'a mut RecallResults,
+    source: StrategemSource,
+    strategem: Strategem,
+    controls: Controls,
+    /// When the code was revealed after a wrong key
+    revealed_at: Option<Instant>,
+    /// Hits and misses of this session, keyed by strategem name
+    session: BTreeMap<&'static str, (usize, usize)>,
+    is_running: bool,
+}
+
+impl<'a> Recall<'a> {
+    pub fn new(results: &'a mut RecallResults, controls: Controls) -> Self {
+        let mut source = StrategemSource::random();
+
+        Self {
+            results,
+            strategem: source.next(),
+            source,
+            controls,
+            revealed_at: None,
+            session: BTreeMap::new(),
+            is_running: true,
+        }
+    }
+
+    pub fn run(&mut self) -> Result<()> {
+        tui::screen::full_clear()?;
+
+        while self.is_running {
+            if crossterm::event::poll(Duration::from_millis(17))? {
+                self.handle_input()?;
+            } else {
+                self.print_frame()?;
+                self.update_state();
+            }
+        }
+
+        self.results.save()?;
+        self.print_summary()
+    }
+
+    fn handle_input(&mut self) -> Result<()> {
+        match crate::event::read(&self.controls)? {
+            Some(Key::Escape) => {
+                tui::screen::clear()?;
+                self.is_running = false;
+            }
+            Some(key) => self.strategem.assert_key(key.into()),
+
+            _ => (),
+        };
+
+        Ok(())
+    }
+
+    fn print_frame(&mut self) -> Result<()> {
+        let (hits, misses) = self
+            .session
+            .values()
+            .fold((0, 0), |acc, rec| (acc.0 + rec.0, acc.1 + rec.1));
+
+        screenln!("\x1b[KRecall (hits: {hits}, misses: {misses})")?;
+        if self.revealed_at.is_some() {
+            screenln!("{}", self.strategem)?;
+            screenln!("\x1b[KMissed! This is the correct code")?;
+        } else {
+            screenln!("{}", self.strategem.masked())?;
+            screenln!("\x1b[KEnter the code from memory")?;
+        }
+        screenln!("Controls: {}", self.controls)?;
+
+        tui::screen::move_back()
+    }
+
+    fn update_state(&mut self) {
+        if let Some(revealed_at) = self.revealed_at {
+            if revealed_at.elapsed() >= REVEAL {
+                self.revealed_at = None;
+                self.strategem = self.source.next();
+            }
+        } else if self.strategem.is_completed() {
+            self.record(true);
+            self.strategem = self.source.next();
+        } else if !self.strategem.is_valid() {
+            self.record(false);
+            self.revealed_at = Some(Instant::now());
+        }
+    }
+
+    fn record(&mut self, hit: bool) {
+        let name = self.strategem.name();
+        let rec = self.session.entry(name).or_default();
+        if hit {
+            rec.0 += 1;
+        } else {
+            rec.1 += 1;
+        }
+
+        self.results.record(name, hit);
+    }
+
+    fn print_summary(&self) -> Result<()> {
+        let _sc = tui::screen::cleaner();
+        tui::screen::full_clear()?;
+
+        let mut session = Vec::from_iter(&self.session);
+        session.sort_by(|a, b| a.1 .1.cmp(&b.1 .1).reverse());
+
+        screenln!("Recall session results:")?;
+        screenln!(
+            "  {:<32} {:>7} {:>7} {:>10}",
+            "Strategem",
+            "Hits",
+            "Misses",
+            "All-time"
+        )?;
+        session.iter().for_each(|(name, (hits, misses))| {
+            let (total_hits, total_misses) = self.results.get(name);
+            let accuracy = total_hits as f64 / (total_hits + total_misses).max(1) as f64 * 100.0;
+            screenln!(
+                "  {:<32} {:>7} {:>7} {:>9.0}%",
+                name,
+                hits,
+                misses,
+                accuracy
+            )
+            .unwrap()
+        });
+
+        tui::confirm_quit(Some("return to training"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategem::StrategemKey;
+
+    #[test]
+    fn misses_reveal_the_code_and_hits_are_recorded() {
+        let mut results = RecallResults::default();
+        let mut recall = Recall::new(&mut results, Controls::arrows());
+        let name = recall.strategem.name();
+
+        let wrong = [StrategemKey::Up, StrategemKey::Down]
+            .into_iter()
+            .find(|key| Some(key) != recall.strategem.next_key().as_ref())
+            .unwrap();
+        recall.strategem.assert_key(wrong);
+        recall.update_state();
+        assert!(recall.revealed_at.is_some());
+        assert_eq!(Some(&(0, 1)), recall.session.get(name));
+
+        recall.revealed_at = None;
+        recall.strategem.reset();
+        while let Some(key) = recall.strategem.next_key() {
+            recall.strategem.assert_key(key);
+        }
+        recall.update_state();
+        assert_eq!(Some(&(1, 1)), recall.session.get(name));
+
+        assert_eq!((1, 1), results.get(name));
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, time::Duration};
 
 use crossterm::event::{KeyCode, KeyEventKind};
 
@@ -8,12 +8,32 @@ pub struct Menu<T: Display> {
     items: Vec<T>,
 }
 
+/// Outcome of [`Menu::exec_until_idle`]
+pub enum Selection {
+    Item(usize),
+    Quit,
+    /// No key was pressed for the whole timeout
+    Idle,
+}
+
 impl<T: Display> Menu<T> {
     pub fn builder() -> MenuBuilder<T> {
         MenuBuilder::new()
     }
 
     pub fn exec(&self, prompt: &str) -> Result<Option<usize>> {
+        match self.select(prompt, None)? {
+            Selection::Item(idx) => Ok(Some(idx)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Like [`Menu::exec`], but gives up once no key was pressed for `timeout`
+    pub fn exec_until_idle(&self, prompt: &str, timeout: Duration) -> Result<Selection> {
+        self.select(prompt, Some(timeout))
+    }
+
+    fn select(&self, prompt: &str, timeout: Option<Duration>) -> Result<Selection> {
         let mut idx: usize = 0;
 
         screenln!("----[ {prompt} ]----------")?;
@@ -24,6 +44,12 @@ impl<T: Display> Menu<T> {
                 screenln!("  [{}] {}", if i == idx { '*' } else { ' ' }, item).unwrap();
             });
 
+            if let Some(timeout) = timeout {
+                if !crossterm::event::poll(timeout)? {
+                    return Ok(Selection::Idle);
+                }
+            }
+
             if let crossterm::event::Event::Key(ev) = crossterm::event::read()? {
                 match ev {
                     crossterm::event::KeyEvent {
@@ -45,12 +71,12 @@ impl<T: Display> Menu<T> {
                         code: KeyCode::Enter,
                         kind: KeyEventKind::Press,
                         ..
-                    } => return Ok(Some(idx)),
+                    } => return Ok(Selection::Item(idx)),
                     crossterm::event::KeyEvent {
                         code: KeyCode::Char('q'),
                         kind: KeyEventKind::Press,
                         ..
-                    } => return Ok(None),
+                    } => return Ok(Selection::Quit),
 
                     _ => (),
                 }
@@ -59,7 +85,7 @@ impl<T: Display> Menu<T> {
             }
         }
 
-        Ok(None)
+        Ok(Selection::Quit)
     }
 }
 
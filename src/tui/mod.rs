@@ -60,3 +60,47 @@ pub fn confirm_action() -> Result<bool> {
 
     Ok(false)
 }
+
+/// Reads a line of text, returns `None` if the player pressed Escape
+pub fn input(prompt: &str) -> Result<Option<String>> {
+    let mut value = String::new();
+
+    loop {
+        let _screen_scope = screen::scope();
+        screenln!("\x1b[K{prompt}: {value}_")?;
+
+        if let Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) = crossterm::event::read()?
+        {
+            match code {
+                KeyCode::Char(ch) => value.push(ch),
+                KeyCode::Backspace => {
+                    value.pop();
+                }
+                KeyCode::Enter if !value.trim().is_empty() => {
+                    return Ok(Some(value.trim().to_string()))
+                }
+                KeyCode::Esc => return Ok(None),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Prints two blocks of text next to each other, the right one starting at `column`
+pub fn columns(left: &str, right: &str, column: u16) -> Result<()> {
+    let left = left.lines().collect::<Vec<_>>();
+    let right = right.lines().collect::<Vec<_>>();
+
+    (0..left.len().max(right.len())).try_for_each(|i| {
+        screenln!(
+            "\x1b[K{}\x1b[{}G\x1b[K{}",
+            left.get(i).unwrap_or(&""),
+            column + 1,
+            right.get(i).unwrap_or(&"")
+        )
+    })
+}
@@ -1,37 +1,48 @@
 use std::{
     fmt::Display,
     path::{Path, PathBuf},
-    time::{Duration, Instant},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use crossterm::style::Stylize;
 
 use crate::{
     error::Result,
-    strategem::{Strategem, StrategemClass, StrategemDifficulty},
+    strategem::{Strategem, StrategemClass},
 };
 
 const VERSION: &str = "0.8";
 
+/// Countdown measured on the run's clock, which is moved forward with [`GameTimer::set_now`]
 pub struct GameTimer {
     initial_duration: Duration,
-    game_over_time: std::time::Instant,
+    game_over_time: Duration,
+    now: Duration,
 }
 
 impl GameTimer {
     pub fn start_from(dur: Duration) -> Self {
         Self {
             initial_duration: dur,
-            game_over_time: std::time::Instant::now() + dur,
+            game_over_time: dur,
+            now: Duration::ZERO,
         }
     }
 
+    pub fn initial_duration(&self) -> Duration {
+        self.initial_duration
+    }
+
+    pub fn set_now(&mut self, now: Duration) {
+        self.now = now;
+    }
+
     pub fn remaining(&self) -> Duration {
-        self.game_over_time - Instant::now()
+        self.game_over_time.saturating_sub(self.now)
     }
 
     pub fn is_over(&self) -> bool {
-        self.game_over_time - Instant::now() <= Duration::ZERO
+        self.remaining() <= Duration::ZERO
     }
 
     pub fn add(&mut self, dur: Duration) {
@@ -39,18 +50,18 @@ impl GameTimer {
     }
 
     pub fn sub(&mut self, dur: Duration) {
-        self.game_over_time -= dur;
+        self.game_over_time = self.game_over_time.saturating_sub(dur);
     }
 
     pub fn reset(&mut self) {
-        self.game_over_time = Instant::now() + self.initial_duration;
+        self.game_over_time = self.now + self.initial_duration;
     }
 }
 
 impl Display for GameTimer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let step = self.initial_duration.as_secs() / 10;
-        let remaining_steps = self.remaining().as_secs() / step + 1;
+        let step = (self.initial_duration.as_millis() / 10).max(1);
+        let remaining_steps = self.remaining().as_millis() / step + 1;
         let time_left = self.remaining();
         let steps_str = "#".repeat(remaining_steps.min(10) as usize);
 
@@ -121,53 +132,102 @@ impl InputFreeze {
     }
 }
 
-pub enum Multiplier {
-    First,
-    Second,
-    Third,
+/// Score multiplier tier reached with the current streak
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Multiplier {
+    /// Position of the tier, the first one being 0
+    tier: usize,
+    value: f64,
+    /// Strategems cleared toward the next tier and how many it takes, `None` on the last tier
+    progress: Option<(usize, usize)>,
 }
 
 impl Multiplier {
-    pub fn get(streak: usize) -> Multiplier {
-        match streak {
-            0..=5 => Multiplier::First,
-            6..=20 => Multiplier::Second,
-            _ => Multiplier::Third,
+    const PROGRESS_LEN: usize = 5;
+
+    pub fn new(tier: usize, value: f64, progress: Option<(usize, usize)>) -> Self {
+        Self {
+            tier,
+            value,
+            progress,
         }
     }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
 }
 
 impl Display for Multiplier {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = format!("{:>4}", format!("x{}", self.value));
         write!(
             f,
             "{}",
-            match self {
-                Multiplier::First => "  ".black(),
-                Multiplier::Second => "x2".green(),
-                Multiplier::Third => "x3".dark_magenta(),
+            match self.tier {
+                0 => label.black(),
+                1 => label.green(),
+                2 => label.dark_magenta(),
+                3 => label.cyan(),
+                _ => label.red(),
             }
-        )
+        )?;
+
+        match self.progress {
+            Some((cleared, needed)) => {
+                let filled = (cleared * Self::PROGRESS_LEN / needed).min(Self::PROGRESS_LEN);
+                write!(
+                    f,
+                    " [{}{}]",
+                    "#".repeat(filled),
+                    " ".repeat(Self::PROGRESS_LEN - filled)
+                )
+            }
+            None => write!(f, " [ {} ]", "MAX".red()),
+        }
     }
 }
 
-pub fn get_score_value(difficulty: &StrategemDifficulty, tier: Multiplier, bonus: usize) -> usize {
-    use Multiplier::*;
-    use StrategemDifficulty::*;
-
-    let base = match (difficulty, tier) {
-        (Easy, First) => 50,
-        (Medium, First) => 75,
-        (Hard, First) => 100,
-        (Easy, Second) => 100,
-        (Medium, Second) => 150,
-        (Hard, Second) => 200,
-        (Easy, Third) => 125,
-        (Medium, Third) => 190,
-        (Hard, Third) => 250,
-    };
-
-    base + bonus
+/// Returns the value following `--<name>` in the command line arguments
+pub fn arg_value(name: &str) -> Option<String> {
+    let flag = format!("--{name}");
+    let mut args = std::env::args().skip_while(|arg| arg.ne(&flag));
+    args.next()?;
+    args.next()
+}
+
+pub fn format_duration(dur: Duration) -> String {
+    format!("{}.{:02}s", dur.as_secs(), dur.subsec_millis() / 10)
+}
+
+pub fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |dur| dur.as_millis() as u64)
+}
+
+/// Days since the Unix epoch in UTC, so every player shares the same day
+pub fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|dur| dur.as_secs() / 86400)
+        .unwrap_or_default()
+}
+
+/// Formats days since the Unix epoch as `YYYY-MM-DD`
+pub fn format_day(day: u64) -> String {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = day as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+
+    format!("{y:04}-{m:02}-{d:02}")
 }
 
 pub fn format_strategem_name(strategem: &Strategem) -> String {
@@ -255,6 +315,22 @@ pub fn setup_data_dir() -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn format_days() {
+        assert_eq!("1970-01-01", format_day(0));
+        assert_eq!("2000-02-29", format_day(11016));
+        assert_eq!("2024-06-08", format_day(19882));
+    }
+
+    #[test]
+    fn short_timers_are_displayed() {
+        let timer = GameTimer::start_from(Duration::from_secs(8));
+        assert!(timer.to_string().ends_with("08.0s"));
+
+        let timer = GameTimer::start_from(Duration::ZERO);
+        assert!(timer.to_string().ends_with("00.0s"));
+    }
+
     #[test]
     #[cfg(target_os = "windows")]
     fn windows_app_data_dir() {